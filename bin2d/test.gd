@@ -7,6 +7,8 @@ func _ready() -> void:
 	test_circle_shape()
 	test_concave_polygon_shape()
 	test_convex_polygon_shape()
+	test_heightmap_shape()
+	test_voxel_shape()
 	print("Success")
 	await get_tree().create_timer(1.0).timeout
 	get_tree().quit()
@@ -28,3 +30,11 @@ func test_concave_polygon_shape():
 func test_convex_polygon_shape():
 	RapierConvexPolygonShapeTests.test_create()
 	RapierConvexPolygonShapeTests.test_set_data()
+
+func test_heightmap_shape():
+	RapierHeightMapShape2DTests.test_create()
+	RapierHeightMapShape2DTests.test_set_data()
+
+func test_voxel_shape():
+	RapierVoxelShapeTests.test_create()
+	RapierVoxelShapeTests.test_set_data()
@@ -7,7 +7,9 @@ func _ready() -> void:
 	test_circle_shape()
 	test_concave_polygon_shape()
 	test_convex_polygon_shape()
+	test_custom_shape()
 	test_cylinder_shape()
+	test_voxel_shape()
 	print("Success")
 	await get_tree().create_timer(1.0).timeout
 	get_tree().quit()
@@ -30,8 +32,16 @@ func test_convex_polygon_shape():  # New function for convex polygon shape
 	RapierConvexPolygonShapeTests.test_create()
 	RapierConvexPolygonShapeTests.test_set_data()
 
+func test_custom_shape():
+	RapierCustomShapeTests.test_create()
+	RapierCustomShapeTests.test_set_data()
+
 func test_cylinder_shape():
 	RapierCylinderShape3DTests.test_create()
 	RapierCylinderShape3DTests.test_set_data_array()
 	RapierCylinderShape3DTests.test_set_data_vector2()
 	RapierCylinderShape3DTests.test_set_data_dictionary()
+
+func test_voxel_shape():
+	RapierVoxelShapeTests.test_create()
+	RapierVoxelShapeTests.test_set_data()
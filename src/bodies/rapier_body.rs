@@ -1748,6 +1748,15 @@ impl RapierBody {
         }
         let prev_mode = self.base.mode;
         self.base.mode = p_mode;
+        if (prev_mode.ord() >= BodyMode::RIGID.ord()) != (p_mode.ord() >= BodyMode::RIGID.ord()) {
+            // Concave shapes may swap to or from their convex decomposition.
+            for i in 0..self.base.get_shape_count() as usize {
+                if !self.base.state.shapes[i].disabled {
+                    self.base
+                        .update_shape_transform(&self.base.state.shapes[i], physics_engine);
+                }
+            }
+        }
         let id = self.base.get_id();
         if let Some(space) = physics_spaces.get_mut(&self.base.get_space(physics_ids)) {
             match p_mode {
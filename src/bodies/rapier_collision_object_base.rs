@@ -259,13 +259,30 @@ impl RapierCollisionObjectBase {
         shape_info.scale = vector_to_rapier(vector_to_godot(shape_info.scale) * scale);
         let position = shape_info.transform.translation * vector_to_rapier(scale);
         shape_info.transform.translation = position;
+        let dynamic = self.collision_object_type == CollisionObjectType::Body
+            && self.mode.ord() >= BodyMode::RIGID.ord();
         physics_engine.collider_set_transform(
             self.state.space_id,
             shape.collider_handle,
             shape_info,
+            dynamic,
         );
     }
 
+    /// Swaps the edited shape into the colliders using it, without recreating them.
+    #[cfg(feature = "dim3")]
+    pub(crate) fn refresh_shape_colliders(
+        &self,
+        shape_id: RapierId,
+        physics_engine: &mut PhysicsEngine,
+    ) {
+        for shape in self.state.shapes.iter() {
+            if shape.id == shape_id {
+                self.update_shape_transform(shape, physics_engine);
+            }
+        }
+    }
+
     pub(super) fn update_transform(&mut self, physics_engine: &mut PhysicsEngine) {
         if !self.is_valid() {
             return;
@@ -1,5 +1,6 @@
 #[cfg(feature = "dim3")]
 pub mod rapier_cone_twist_joint_3d;
+pub mod rapier_coupling_joint;
 #[cfg(feature = "dim2")]
 pub mod rapier_damped_spring_joint_2d;
 pub mod rapier_empty_joint;
@@ -16,3 +17,5 @@ pub mod rapier_rope_joint;
 pub mod rapier_slider_joint_3d;
 #[cfg(feature = "dim3")]
 pub mod rapier_spherical_joint_3d;
+#[cfg(feature = "dim3")]
+pub mod rapier_spring_joint_3d;
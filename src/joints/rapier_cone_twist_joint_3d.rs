@@ -72,6 +72,7 @@ impl RapierConeTwistJoint3D {
             twist_span,
             joint_type,
             true,
+            &RapierJointBase::user_data(id),
         );
         Self {
             base: RapierJointBase::new(id, rid, space_id, space_handle, handle, joint_type),
@@ -0,0 +1,105 @@
+use godot::classes::*;
+use godot::prelude::*;
+#[cfg(feature = "dim2")]
+use physics_server_2d::JointType;
+#[cfg(feature = "dim3")]
+use physics_server_3d::JointType;
+
+use super::rapier_joint_base::RapierJointBase;
+use super::rapier_joint_base::RapierJointType;
+use crate::bodies::rapier_collision_object::IRapierCollisionObject;
+use crate::bodies::rapier_collision_object::RapierCollisionObject;
+use crate::joints::rapier_joint::IRapierJoint;
+use crate::joints::rapier_joint::impl_rapier_joint_base;
+use crate::rapier_wrapper::joint::CouplingAxis;
+use crate::rapier_wrapper::joint::JointCoupling;
+use crate::rapier_wrapper::prelude::*;
+use crate::servers::rapier_physics_singleton::RapierId;
+use crate::types::*;
+/// Which velocities of the two bodies a [`RapierCouplingJoint`] ties together.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RapierCouplingKind {
+    /// Rotation of body A drives rotation of body B.
+    Gear,
+    /// Rotation of body A drives translation of body B.
+    RackAndPinion,
+    /// Translation of body A drives translation of body B.
+    Pulley,
+}
+/// Keeps the motion of body B at `ratio` times the motion of body A, along axes given in world
+/// space at creation. Its rapier joint leaves every axis free, the space corrects the velocities
+/// of the bodies before each step, and not at all while the rapier joint is disabled.
+pub struct RapierCouplingJoint {
+    base: RapierJointBase,
+}
+impl RapierCouplingJoint {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: RapierId,
+        rid: Rid,
+        kind: RapierCouplingKind,
+        axis_a: Vector,
+        axis_b: Vector,
+        ratio: real,
+        body_a: &RapierCollisionObject,
+        body_b: &RapierCollisionObject,
+        physics_engine: &mut PhysicsEngine,
+    ) -> Self {
+        let invalid_joint = Self {
+            base: RapierJointBase::default(),
+        };
+        if body_a.get_base().get_rid() == body_b.get_base().get_rid() {
+            return invalid_joint;
+        }
+        if !body_a.get_base().is_valid()
+            || !body_b.get_base().is_valid()
+            || body_a.get_base().get_space_id() != body_b.get_base().get_space_id()
+        {
+            return invalid_joint;
+        }
+        let (angular_a, angular_b) = match kind {
+            RapierCouplingKind::Gear => (true, true),
+            RapierCouplingKind::RackAndPinion => (true, false),
+            RapierCouplingKind::Pulley => (false, false),
+        };
+        let space_handle = body_a.get_base().get_space_id();
+        let space_id = body_a.get_base().get_space_id();
+        let handle = physics_engine.joint_create_free(
+            space_handle,
+            body_a.get_base().get_body_handle(),
+            body_b.get_base().get_body_handle(),
+            &RapierJointBase::user_data(id),
+        );
+        let mut base = RapierJointBase::new(
+            id,
+            rid,
+            space_id,
+            space_handle,
+            handle,
+            RapierJointType::Impulse,
+        );
+        // Gears and pulleys may touch, so coupled bodies keep colliding.
+        base.disable_collisions_between_bodies(false, physics_engine);
+        base.set_coupling(Some(JointCoupling {
+            body_1: body_a.get_base().get_body_handle(),
+            body_2: body_b.get_base().get_body_handle(),
+            axis_1: CouplingAxis {
+                angular: angular_a,
+                local_axis: Self::world_to_local_axis(body_a, axis_a),
+            },
+            axis_2: CouplingAxis {
+                angular: angular_b,
+                local_axis: Self::world_to_local_axis(body_b, axis_b),
+            },
+            ratio,
+            tracking: None,
+        }));
+        Self { base }
+    }
+
+    fn world_to_local_axis(body: &RapierCollisionObject, axis: Vector) -> rapier::math::Vector {
+        transform_rotation_rapier(&body.get_base().get_transform()).inverse()
+            * vector_to_rapier(vector_normalized(axis))
+    }
+}
+impl_rapier_joint_base!(RapierCouplingJoint, JointType::MAX);
@@ -65,6 +65,7 @@ impl RapierDampedSpringJoint2D {
             rest_length,
             joint_type,
             true,
+            &RapierJointBase::user_data(id),
         );
         Self {
             rest_length,
@@ -55,6 +55,7 @@ impl RapierFixedJoint {
             vector_to_rapier(anchor_b),
             joint_type,
             true,
+            &RapierJointBase::user_data(id),
         );
         Self {
             base: RapierJointBase::new(id, rid, space_id, space_handle, handle, joint_type),
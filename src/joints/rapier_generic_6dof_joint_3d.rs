@@ -122,6 +122,7 @@ impl RapierGeneric6DOFJoint3D {
             rapier_axis_b,
             joint_type,
             true,
+            &RapierJointBase::user_data(id),
         );
         Self {
             base: RapierJointBase::new(id, rid, space_id, space_handle, handle, joint_type),
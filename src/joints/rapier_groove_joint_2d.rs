@@ -66,6 +66,7 @@ impl RapierGrooveJoint2D {
             rapier_limits,
             joint_type,
             true,
+            &RapierJointBase::user_data(id),
         );
         Self {
             base: RapierJointBase::new(id, rid, space_id, space_handle, handle, joint_type),
@@ -11,6 +11,7 @@ use physics_server_3d::JointType;
 
 #[cfg(feature = "dim3")]
 use super::rapier_cone_twist_joint_3d::RapierConeTwistJoint3D;
+use super::rapier_coupling_joint::RapierCouplingJoint;
 #[cfg(feature = "dim2")]
 use super::rapier_damped_spring_joint_2d::RapierDampedSpringJoint2D;
 use super::rapier_fixed_joint::RapierFixedJoint;
@@ -23,6 +24,8 @@ use super::rapier_rope_joint::RapierRopeJoint;
 use super::rapier_slider_joint_3d::RapierSliderJoint3D;
 #[cfg(feature = "dim3")]
 use super::rapier_spherical_joint_3d::RapierSphericalJoint3D;
+#[cfg(feature = "dim3")]
+use super::rapier_spring_joint_3d::RapierSpringJoint3D;
 #[cfg(feature = "dim2")]
 use crate::rapier_wrapper::prelude::PhysicsEngine;
 use crate::*;
@@ -53,6 +56,7 @@ pub enum RapierJoint {
     RapierEmptyJoint(RapierEmptyJoint),
     RapierFixedJoint(RapierFixedJoint),
     RapierRopeJoint(RapierRopeJoint),
+    RapierCouplingJoint(RapierCouplingJoint),
     RapierRevoluteJoint(RapierRevoluteJoint),
     #[cfg(feature = "dim3")]
     RapierSliderJoint3D(RapierSliderJoint3D),
@@ -61,6 +65,8 @@ pub enum RapierJoint {
     #[cfg(feature = "dim3")]
     RapierSphericalJoint3D(RapierSphericalJoint3D),
     #[cfg(feature = "dim3")]
+    RapierSpringJoint3D(RapierSpringJoint3D),
+    #[cfg(feature = "dim3")]
     RapierGeneric6DOFJoint3D(RapierGeneric6DOFJoint3D),
 }
 macro_rules! impl_rapier_joint_trait {
@@ -111,10 +117,12 @@ impl_rapier_joint_trait!(
     RapierEmptyJoint,
     RapierFixedJoint,
     RapierRopeJoint,
+    RapierCouplingJoint,
     RapierRevoluteJoint,
     RapierSliderJoint3D,
     RapierConeTwistJoint3D,
     RapierSphericalJoint3D,
+    RapierSpringJoint3D,
     RapierGeneric6DOFJoint3D
 );
 #[cfg(feature = "dim2")]
@@ -125,6 +133,7 @@ impl_rapier_joint_trait!(
     RapierEmptyJoint,
     RapierFixedJoint,
     RapierRopeJoint,
+    RapierCouplingJoint,
     RapierRevoluteJoint
 );
 pub trait IRapierJoint {
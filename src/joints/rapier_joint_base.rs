@@ -11,6 +11,7 @@ use crate::bodies::exportable_object::ExportableObject;
 use crate::bodies::exportable_object::ImportToExport;
 #[cfg(feature = "serde-serialize")]
 use crate::bodies::exportable_object::ObjectImportState;
+use crate::rapier_wrapper::joint::JointCoupling;
 use crate::rapier_wrapper::prelude::*;
 use crate::*;
 #[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
@@ -51,6 +52,30 @@ impl ImportToExport for JointImport {
         JointExport { state: &self.state }
     }
 }
+/// Parameters of a spring joint, kept with the joint state so they survive export.
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct RapierSpringState {
+    pub rest_length: Real,
+    pub stiffness: Real,
+    pub damping: Real,
+    pub min_length: Option<Real>,
+    pub max_length: Option<Real>,
+}
+impl Default for RapierSpringState {
+    fn default() -> Self {
+        Self {
+            rest_length: 0.0,
+            stiffness: 20.0,
+            damping: 1.0,
+            min_length: None,
+            max_length: None,
+        }
+    }
+}
 #[cfg_attr(
     feature = "serde-serialize",
     derive(serde::Serialize, serde::Deserialize)
@@ -62,10 +87,17 @@ pub struct RapierJointBaseState {
     space_handle: WorldHandle,
     space_id: RapierId,
     joint_type: RapierJointType,
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    coupling: Option<JointCoupling>,
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    spring: Option<RapierSpringState>,
 }
 pub struct RapierJointBase {
     rid: Rid,
     max_force: f32,
+    break_impulse: Real,
+    break_angular_impulse: Real,
+    broken: bool,
     disabled_collisions_between_bodies: bool,
     state: RapierJointBaseState,
     pub custom_ik_options: InverseKinematicsOption,
@@ -113,6 +145,9 @@ impl RapierJointBase {
         Self {
             rid,
             max_force: f32::MAX,
+            break_impulse: Real::INFINITY,
+            break_angular_impulse: Real::INFINITY,
+            broken: false,
             disabled_collisions_between_bodies: true,
             state: RapierJointBaseState {
                 id,
@@ -120,11 +155,21 @@ impl RapierJointBase {
                 space_handle,
                 space_id,
                 joint_type,
+                coupling: None,
+                spring: None,
             },
             custom_ik_options: InverseKinematicsOption::default(),
         }
     }
 
+    /// User data of the rapier joint, leading back to the joint through its id.
+    pub fn user_data(id: RapierId) -> UserData {
+        UserData {
+            part1: id,
+            part2: 0,
+        }
+    }
+
     pub fn get_handle(&self) -> JointHandle {
         self.state.handle
     }
@@ -157,6 +202,64 @@ impl RapierJointBase {
         self.max_force
     }
 
+    /// Setting a threshold also re-arms a joint that already broke, enabling it again.
+    pub fn set_break_threshold(
+        &mut self,
+        max_impulse: Real,
+        max_angular_impulse: Real,
+        physics_engine: &mut PhysicsEngine,
+    ) {
+        self.break_impulse = max_impulse;
+        self.break_angular_impulse = max_angular_impulse;
+        if self.broken && self.is_valid() {
+            physics_engine.joint_set_enabled(self.state.space_handle, self.state.handle, true);
+        }
+        self.broken = false;
+    }
+
+    pub fn get_break_threshold(&self) -> (Real, Real) {
+        (self.break_impulse, self.break_angular_impulse)
+    }
+
+    pub fn has_break_threshold(&self) -> bool {
+        self.break_impulse.is_finite() || self.break_angular_impulse.is_finite()
+    }
+
+    pub fn is_breakable(&self) -> bool {
+        !self.broken && self.has_break_threshold()
+    }
+
+    pub fn exceeds_break_threshold(&self, impulse: Real, angular_impulse: Real) -> bool {
+        impulse > self.break_impulse || angular_impulse > self.break_angular_impulse
+    }
+
+    pub fn set_broken(&mut self, broken: bool) {
+        self.broken = broken;
+    }
+
+    pub fn is_broken(&self) -> bool {
+        self.broken
+    }
+
+    pub fn set_coupling(&mut self, coupling: Option<JointCoupling>) {
+        self.state.coupling = coupling;
+    }
+
+    /// Couplings are solved by the space each step, their rapier joint leaves every axis free.
+    pub fn get_mut_coupling(&mut self) -> Option<&mut JointCoupling> {
+        self.state.coupling.as_mut()
+    }
+
+    #[cfg(feature = "dim3")]
+    pub fn set_spring(&mut self, spring: Option<RapierSpringState>) {
+        self.state.spring = spring;
+    }
+
+    #[cfg(feature = "dim3")]
+    pub fn get_spring(&self) -> Option<RapierSpringState> {
+        self.state.spring
+    }
+
     pub fn is_valid(&self) -> bool {
         self.state.space_handle != WorldHandle::default()
             && self.state.handle != JointHandle::default()
@@ -189,12 +292,14 @@ impl RapierJointBase {
         self.disabled_collisions_between_bodies
     }
 
+    /// The broken flag is not copied, as the joint it is copied to is a new, enabled one.
     pub fn copy_settings_from(
         &mut self,
         joint: &RapierJointBase,
         physics_engine: &mut PhysicsEngine,
     ) {
         self.set_max_force(joint.get_max_force());
+        (self.break_impulse, self.break_angular_impulse) = joint.get_break_threshold();
         self.disable_collisions_between_bodies(
             joint.is_disabled_collisions_between_bodies(),
             physics_engine,
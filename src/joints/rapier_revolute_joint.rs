@@ -100,6 +100,7 @@ impl RapierRevoluteJoint {
             false,
             f32::MAX,
             true,
+            &RapierJointBase::user_data(id),
         );
         Self {
             angular_limit_lower: 0.0,
@@ -181,6 +182,7 @@ impl RapierRevoluteJoint {
             false,
             0.0,
             true,
+            &RapierJointBase::user_data(id),
         );
         Self {
             angular_limit_lower: 0.0,
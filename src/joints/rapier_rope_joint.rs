@@ -59,6 +59,7 @@ impl RapierRopeJoint {
             max_distance,
             joint_type,
             true,
+            &RapierJointBase::user_data(id),
         );
         Self {
             max_distance,
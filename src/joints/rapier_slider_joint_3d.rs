@@ -64,6 +64,7 @@ impl RapierSliderJoint3D {
             0.0,
             joint_type,
             true,
+            &RapierJointBase::user_data(id),
         );
         Self {
             base: RapierJointBase::new(id, rid, space_id, space_handle, handle, joint_type),
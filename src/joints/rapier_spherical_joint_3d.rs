@@ -61,6 +61,7 @@ impl RapierSphericalJoint3D {
             rapier_anchor_b,
             joint_type,
             true,
+            &RapierJointBase::user_data(id),
         );
         Self {
             anchor_a,
@@ -0,0 +1,123 @@
+use godot::classes::physics_server_3d::JointType;
+use godot::prelude::*;
+
+use super::rapier_joint_base::RapierJointBase;
+use super::rapier_joint_base::RapierJointType;
+use super::rapier_joint_base::RapierSpringState;
+use crate::bodies::rapier_collision_object::IRapierCollisionObject;
+use crate::bodies::rapier_collision_object::RapierCollisionObject;
+use crate::joints::rapier_joint::IRapierJoint;
+use crate::joints::rapier_joint::impl_rapier_joint_base;
+use crate::rapier_wrapper::prelude::*;
+use crate::servers::rapier_physics_server_extra::RapierSpringParam;
+use crate::servers::rapier_physics_singleton::RapierId;
+use crate::types::*;
+/// Pulls two bodies towards `rest_length` of each other, optionally bounded between a minimum and
+/// maximum length. The parameters live in the joint state so they are exported with it.
+pub struct RapierSpringJoint3D {
+    base: RapierJointBase,
+}
+impl RapierSpringJoint3D {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: RapierId,
+        rid: Rid,
+        p_anchor_a: Vector,
+        p_anchor_b: Vector,
+        body_a: &RapierCollisionObject,
+        body_b: &RapierCollisionObject,
+        physics_engine: &mut PhysicsEngine,
+        joint_type: RapierJointType,
+    ) -> Self {
+        let invalid_joint = Self {
+            base: RapierJointBase::default(),
+        };
+        if body_a.get_base().get_rid() == body_b.get_base().get_rid() {
+            return invalid_joint;
+        }
+        if !body_a.get_base().is_valid()
+            || !body_b.get_base().is_valid()
+            || body_a.get_base().get_space_id() != body_b.get_base().get_space_id()
+        {
+            return invalid_joint;
+        }
+        let anchor_a = world_to_local_no_scale(&body_a.get_base().get_transform(), p_anchor_a);
+        let anchor_b = world_to_local_no_scale(&body_b.get_base().get_transform(), p_anchor_b);
+        let spring = RapierSpringState {
+            rest_length: vector_length(p_anchor_a - p_anchor_b),
+            ..Default::default()
+        };
+        let space_handle = body_a.get_base().get_space_id();
+        let space_id = body_a.get_base().get_space_id();
+        let handle = physics_engine.joint_create_spring(
+            space_handle,
+            body_a.get_base().get_body_handle(),
+            body_b.get_base().get_body_handle(),
+            vector_to_rapier(anchor_a),
+            vector_to_rapier(anchor_b),
+            spring.stiffness,
+            spring.damping,
+            spring.rest_length,
+            joint_type,
+            true,
+            &RapierJointBase::user_data(id),
+        );
+        let mut base = RapierJointBase::new(id, rid, space_id, space_handle, handle, joint_type);
+        base.set_spring(Some(spring));
+        Self { base }
+    }
+
+    /// Negative lengths clear the minimum or maximum length.
+    pub fn set_param(
+        &mut self,
+        param: RapierSpringParam,
+        value: real,
+        physics_engine: &mut PhysicsEngine,
+    ) {
+        let mut spring = self.base.get_spring().unwrap_or_default();
+        let length = (value >= 0.0).then_some(value);
+        match param {
+            RapierSpringParam::RestLength => spring.rest_length = value,
+            RapierSpringParam::Stiffness => spring.stiffness = value,
+            RapierSpringParam::Damping => spring.damping = value,
+            RapierSpringParam::MinLength => spring.min_length = length,
+            RapierSpringParam::MaxLength => spring.max_length = length,
+        }
+        self.base.set_spring(Some(spring));
+        if !self.base.is_valid() {
+            return;
+        }
+        match param {
+            RapierSpringParam::MinLength | RapierSpringParam::MaxLength => {
+                physics_engine.joint_set_spring_limits(
+                    self.base.get_space_id(),
+                    self.base.get_handle(),
+                    spring.min_length,
+                    spring.max_length,
+                );
+            }
+            _ => {
+                physics_engine.joint_change_spring_params(
+                    self.base.get_space_id(),
+                    self.base.get_handle(),
+                    spring.stiffness,
+                    spring.damping,
+                    spring.rest_length,
+                );
+            }
+        }
+    }
+
+    /// Unset minimum and maximum lengths read as `-1`.
+    pub fn get_param(&self, param: RapierSpringParam) -> real {
+        let spring = self.base.get_spring().unwrap_or_default();
+        match param {
+            RapierSpringParam::RestLength => spring.rest_length,
+            RapierSpringParam::Stiffness => spring.stiffness,
+            RapierSpringParam::Damping => spring.damping,
+            RapierSpringParam::MinLength => spring.min_length.unwrap_or(-1.0),
+            RapierSpringParam::MaxLength => spring.max_length.unwrap_or(-1.0),
+        }
+    }
+}
+impl_rapier_joint_base!(RapierSpringJoint3D, JointType::MAX);
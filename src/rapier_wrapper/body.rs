@@ -1,6 +1,7 @@
 use rapier::prelude::*;
 
 use super::ANG_ZERO;
+use crate::joints::rapier_joint_base::RapierJointType;
 use crate::rapier_wrapper::prelude::*;
 pub enum BodyType {
     Dynamic,
@@ -58,6 +59,53 @@ impl PhysicsEngine {
         }
     }
 
+    /// Joints of both joint sets attached to the body, each with the body on its other end and
+    /// the joint's user data. Multibody joints are reported as [`RapierJointType::MultiBody`].
+    pub fn body_get_attached_joints(
+        &self,
+        world_handle: WorldHandle,
+        body_handle: RigidBodyHandle,
+    ) -> Vec<(JointHandle, RigidBodyHandle, UserData)> {
+        let mut result = Vec::new();
+        let Some(physics_world) = self.get_world(world_handle) else {
+            return result;
+        };
+        let other_body = |rb1: RigidBodyHandle, rb2: RigidBodyHandle| {
+            if rb1 == body_handle { rb2 } else { rb1 }
+        };
+        for (rb1, rb2, joint_handle, joint) in physics_world
+            .physics_objects
+            .impulse_joint_set
+            .attached_joints(body_handle)
+        {
+            result.push((
+                JointHandle {
+                    index: joint_handle.0,
+                    joint_type: RapierJointType::Impulse,
+                },
+                other_body(rb1, rb2),
+                UserData::new(joint.data.user_data),
+            ));
+        }
+        let multibody_joint_set = &physics_world.physics_objects.multibody_joint_set;
+        for (rb1, rb2, joint_handle) in multibody_joint_set.attached_joints(body_handle) {
+            let user_data = multibody_joint_set
+                .get(joint_handle)
+                .and_then(|(multibody, link_id)| multibody.link(link_id))
+                .map_or(0, |link| link.joint.data.user_data);
+            let user_data = UserData::new(user_data);
+            result.push((
+                JointHandle {
+                    index: joint_handle.0,
+                    joint_type: RapierJointType::MultiBody,
+                },
+                other_body(rb1, rb2),
+                user_data,
+            ));
+        }
+        result
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn body_create(
         &mut self,
@@ -111,6 +159,17 @@ impl PhysicsEngine {
         }
     }
 
+    pub fn body_get_user_data(
+        &self,
+        world_handle: WorldHandle,
+        body_handle: RigidBodyHandle,
+    ) -> UserData {
+        if let Some(physics_world) = self.get_world(world_handle) {
+            return physics_world.get_rigid_body_user_data(body_handle);
+        }
+        UserData::invalid_user_data()
+    }
+
     pub fn body_change_mode(
         &mut self,
         world_handle: WorldHandle,
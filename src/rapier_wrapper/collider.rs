@@ -1,5 +1,6 @@
 use godot::builtin::math::FloatExt;
 use godot::global::godot_error;
+use rapier::parry::shape::RoundShape;
 use rapier::prelude::*;
 use salva::integrations::rapier::ColliderSampling;
 use salva::object::Boundary;
@@ -88,11 +89,397 @@ pub fn skew_shape(shape: &SharedShape, shape_info: ShapeInfo) -> SharedShape {
     }
     shape.clone()
 }
+/// Points on the surface of a convex shape, subdivided like scaled balls. `None` for shapes that
+/// are not convex.
 #[cfg(feature = "dim3")]
-fn skew_shape(shape: &SharedShape, _shape_info: ShapeInfo) -> SharedShape {
+fn convex_shape_points(shape: &SharedShape) -> Option<Vec<Vector>> {
+    let subdivisions =
+        crate::servers::rapier_project_settings::motion_settings().shape_scale_subdivisions;
+    match shape.shape_type() {
+        ShapeType::Ball => shape
+            .as_ball()
+            .map(|ball| ball.to_trimesh(subdivisions, subdivisions).0),
+        ShapeType::Capsule => shape
+            .as_capsule()
+            .map(|capsule| capsule.to_trimesh(subdivisions, subdivisions).0),
+        ShapeType::Cylinder => shape
+            .as_cylinder()
+            .map(|cylinder| cylinder.to_trimesh(subdivisions).0),
+        ShapeType::Cone => shape.as_cone().map(|cone| cone.to_trimesh(subdivisions).0),
+        ShapeType::Cuboid => shape.as_cuboid().map(|cuboid| cuboid.to_trimesh().0),
+        ShapeType::ConvexPolyhedron => shape
+            .as_convex_polyhedron()
+            .map(|convex_polyhedron| convex_polyhedron.points().to_vec()),
+        _ => None,
+    }
+}
+/// Set once a shape that cannot be sheared was reported, as queries would report it every time.
+#[cfg(feature = "dim3")]
+static SKEW_UNSUPPORTED_REPORTED: std::sync::atomic::AtomicBool =
+    std::sync::atomic::AtomicBool::new(false);
+/// Moves the points of a convex, triangle or segment shape with `transform_point`. A rounded
+/// shape has its inner shape moved and keeps its border. `None` for any other shape.
+#[cfg(feature = "dim3")]
+fn skew_convex_shape(
+    shape: &SharedShape,
+    transform_point: &impl Fn(Vector) -> Vector,
+) -> Option<SharedShape> {
+    if let Some((inner_shape, border_radius)) = unround_shape(shape) {
+        return skew_convex_shape(&inner_shape, transform_point)
+            .map(|inner_shape| round_shape(&inner_shape, border_radius));
+    }
+    match shape.shape_type() {
+        ShapeType::Triangle => shape.as_triangle().map(|triangle| {
+            SharedShape::triangle(
+                transform_point(triangle.a),
+                transform_point(triangle.b),
+                transform_point(triangle.c),
+            )
+        }),
+        ShapeType::Segment => shape.as_segment().map(|segment| {
+            SharedShape::segment(transform_point(segment.a), transform_point(segment.b))
+        }),
+        _ => {
+            let points: Vec<Vector> = convex_shape_points(shape)?
+                .into_iter()
+                .map(transform_point)
+                .collect();
+            SharedShape::convex_hull(&points)
+        }
+    }
+}
+/// Shears a 3D shape before it is scaled. Convex shapes become the convex hull of their
+/// sheared surface points, as 2D does with polylines. Shapes that cannot be sheared, such as
+/// heightfields and voxels, are used unsheared.
+#[cfg(feature = "dim3")]
+pub fn skew_shape(shape: &SharedShape, shape_info: ShapeInfo) -> SharedShape {
+    let skew = shape_info.skew;
+    if skew == Mat3::IDENTITY {
+        return shape.clone();
+    }
+    match shape.shape_type() {
+        ShapeType::Compound => {
+            if let Some(compound) = shape.as_compound() {
+                // The shear does not keep the parts' rotations, so their points are moved into
+                // the compound's frame and the parts placed at its origin.
+                let mut skewed_shapes = Vec::new();
+                for (position, sub_shape) in compound.shapes() {
+                    let Some(skewed_sub_shape) =
+                        skew_convex_shape(sub_shape, &|point| skew * (*position * point))
+                    else {
+                        report_unsupported_skew(sub_shape.shape_type());
+                        return shape.clone();
+                    };
+                    skewed_shapes.push((
+                        Pose::from_parts(Vector::ZERO, Rotation::default()),
+                        skewed_sub_shape,
+                    ));
+                }
+                return SharedShape::compound(skewed_shapes);
+            }
+        }
+        ShapeType::TriMesh => {
+            if let Some(trimesh) = shape.as_trimesh() {
+                let vertices = trimesh
+                    .vertices()
+                    .iter()
+                    .map(|vertex| skew * *vertex)
+                    .collect();
+                if let Ok(skewed_shape) = SharedShape::trimesh_with_flags(
+                    vertices,
+                    trimesh.indices().to_vec(),
+                    trimesh.flags(),
+                ) {
+                    return skewed_shape;
+                }
+            }
+        }
+        _ => {
+            if let Some(skewed_shape) = skew_convex_shape(shape, &|point| skew * point) {
+                return skewed_shape;
+            }
+        }
+    }
+    report_unsupported_skew(shape.shape_type());
     shape.clone()
 }
+#[cfg(feature = "dim3")]
+fn report_unsupported_skew(shape_type: ShapeType) {
+    if !SKEW_UNSUPPORTED_REPORTED.swap(true, std::sync::atomic::Ordering::Relaxed) {
+        godot_error!(
+            "Shape type {:?} not supported for skewing, it is used without the shear",
+            shape_type
+        );
+    }
+}
+/// Scaling only stretches the pseudo-normals of an oriented trimesh, which is wrong for a
+/// non-uniform scale and turns them inwards for a mirroring one, so they are recomputed from the
+/// scaled mesh with the same flags.
+#[cfg(feature = "dim3")]
+fn scale_trimesh(trimesh: &TriMesh, scale: Vector) -> TriMesh {
+    let mut trimesh = trimesh.clone().scaled(scale);
+    let flags = trimesh.flags();
+    let oriented = TriMeshFlags::ORIENTED | TriMeshFlags::FIX_INTERNAL_EDGES;
+    if !flags.intersects(oriented) || (scale.x == scale.y && scale.y == scale.z && scale.x > 0.0) {
+        return trimesh;
+    }
+    if scale.x * scale.y * scale.z < 0.0 {
+        trimesh.reverse();
+    }
+    let _ = trimesh.set_flags(flags - oriented);
+    let _ = trimesh.set_flags(flags);
+    trimesh
+}
+/// Wraps the convex shapes parry can round in their rounded counterpart, growing them by
+/// `border_radius` in every direction. The parts of a compound are rounded one by one, and any
+/// other shape is returned as is.
+pub fn round_shape(shape: &SharedShape, border_radius: Real) -> SharedShape {
+    if border_radius <= 0.0 {
+        return shape.clone();
+    }
+    match shape.shape_type() {
+        ShapeType::Cuboid => {
+            if let Some(cuboid) = shape.as_cuboid() {
+                return SharedShape::new(RoundShape {
+                    inner_shape: *cuboid,
+                    border_radius,
+                });
+            }
+        }
+        #[cfg(feature = "dim2")]
+        ShapeType::ConvexPolygon => {
+            if let Some(convex_polygon) = shape.as_convex_polygon() {
+                return SharedShape::new(RoundShape {
+                    inner_shape: convex_polygon.clone(),
+                    border_radius,
+                });
+            }
+        }
+        #[cfg(feature = "dim3")]
+        ShapeType::ConvexPolyhedron => {
+            if let Some(convex_polyhedron) = shape.as_convex_polyhedron() {
+                return SharedShape::new(RoundShape {
+                    inner_shape: convex_polyhedron.clone(),
+                    border_radius,
+                });
+            }
+        }
+        #[cfg(feature = "dim3")]
+        ShapeType::Cylinder => {
+            if let Some(cylinder) = shape.as_cylinder() {
+                return SharedShape::new(RoundShape {
+                    inner_shape: *cylinder,
+                    border_radius,
+                });
+            }
+        }
+        #[cfg(feature = "dim3")]
+        ShapeType::Cone => {
+            if let Some(cone) = shape.as_cone() {
+                return SharedShape::new(RoundShape {
+                    inner_shape: *cone,
+                    border_radius,
+                });
+            }
+        }
+        ShapeType::Triangle => {
+            if let Some(triangle) = shape.as_triangle() {
+                return SharedShape::new(RoundShape {
+                    inner_shape: *triangle,
+                    border_radius,
+                });
+            }
+        }
+        ShapeType::Compound => {
+            if let Some(compound) = shape.as_compound() {
+                let shapes = compound
+                    .shapes()
+                    .iter()
+                    .map(|(position, shape)| (*position, round_shape(shape, border_radius)))
+                    .collect();
+                return SharedShape::compound(shapes);
+            }
+        }
+        _ => {}
+    }
+    shape.clone()
+}
+/// Smallest fraction of its size a convex hull is shrunk to, so a large border never collapses
+/// it to a point.
+const MIN_HULL_SHRINK: Real = 0.01;
+/// The points of a convex polygon or polyhedron, their centroid and the distance from it to the
+/// closest face, which is not positive for a flat hull.
+fn convex_inner_distance(shape: &dyn Shape) -> Option<(&[Vector], Vector, Real)> {
+    #[cfg(feature = "dim2")]
+    let (points, distances) = {
+        let polygon = shape.as_convex_polygon()?;
+        let points = polygon.points();
+        let center = points.iter().copied().sum::<Vector>() / points.len() as Real;
+        let distances: Vec<Real> = points
+            .iter()
+            .zip(polygon.normals())
+            .map(|(point, normal)| normal.dot(*point - center))
+            .collect();
+        (points, (center, distances))
+    };
+    #[cfg(feature = "dim3")]
+    let (points, distances) = {
+        let polyhedron = shape.as_convex_polyhedron()?;
+        let points = polyhedron.points();
+        let center = points.iter().copied().sum::<Vector>() / points.len() as Real;
+        let vertices = polyhedron.vertices_adj_to_face();
+        let distances: Vec<Real> = polyhedron
+            .faces()
+            .iter()
+            .map(|face| {
+                let point = points[vertices[face.first_vertex_or_edge as usize] as usize];
+                face.normal.dot(point - center)
+            })
+            .collect();
+        (points, (center, distances))
+    };
+    let (center, distances) = distances;
+    let distance = distances.into_iter().fold(Real::MAX, Real::min);
+    Some((points, center, distance))
+}
+fn convex_hull_from_points(points: Vec<Vector>) -> Option<SharedShape> {
+    #[cfg(feature = "dim2")]
+    return SharedShape::convex_polyline(points);
+    #[cfg(feature = "dim3")]
+    return SharedShape::convex_hull(&points);
+}
+/// Rounds `shape` like [`round_shape`], but shrinks it by `border_radius` first so the rounded
+/// shape keeps its size. Boxes and cylinders shrink exactly and cones along their sides. Convex
+/// hulls shrink towards their centroid until their closest face moved by the radius, which
+/// moves the farther faces a bit more. The radius is clamped to what the shape can lose, and
+/// flat shapes such as triangles cannot shrink and grow by it.
+pub fn round_shape_inside(shape: &SharedShape, border_radius: Real) -> SharedShape {
+    if border_radius <= 0.0 {
+        return shape.clone();
+    }
+    match shape.shape_type() {
+        ShapeType::Cuboid => {
+            if let Some(cuboid) = shape.as_cuboid() {
+                let border_radius = border_radius.min(cuboid.half_extents.min_element());
+                let inner_shape = Cuboid::new(cuboid.half_extents - Vector::splat(border_radius));
+                return round_shape(&SharedShape::new(inner_shape), border_radius);
+            }
+        }
+        #[cfg(feature = "dim3")]
+        ShapeType::Cylinder => {
+            if let Some(cylinder) = shape.as_cylinder() {
+                let border_radius = border_radius.min(cylinder.half_height).min(cylinder.radius);
+                let inner_shape = SharedShape::cylinder(
+                    cylinder.half_height - border_radius,
+                    cylinder.radius - border_radius,
+                );
+                return round_shape(&inner_shape, border_radius);
+            }
+        }
+        #[cfg(feature = "dim3")]
+        ShapeType::Cone => {
+            if let Some(cone) = shape.as_cone() {
+                // A similar cone whose sides are `border_radius` inside the original ones. Its
+                // base moves up a bit more, as the cone stays centered.
+                let slant = cone.radius.hypot(2.0 * cone.half_height);
+                if slant > 0.0 {
+                    let sin_angle = cone.radius / slant;
+                    let border_radius = border_radius.min(cone.half_height * sin_angle);
+                    let half_height = cone.half_height - border_radius / sin_angle;
+                    let inner_shape = SharedShape::cone(
+                        half_height,
+                        cone.radius * half_height / cone.half_height,
+                    );
+                    return round_shape(&inner_shape, border_radius);
+                }
+            }
+        }
+        ShapeType::Compound => {
+            if let Some(compound) = shape.as_compound() {
+                let shapes = compound
+                    .shapes()
+                    .iter()
+                    .map(|(position, shape)| (*position, round_shape_inside(shape, border_radius)))
+                    .collect();
+                return SharedShape::compound(shapes);
+            }
+        }
+        _ => {
+            if let Some((points, center, distance)) = convex_inner_distance(shape.as_ref())
+                && distance > 0.0
+            {
+                let shrink = (1.0 - border_radius / distance).max(MIN_HULL_SHRINK);
+                let points = points
+                    .iter()
+                    .map(|point| center + (*point - center) * shrink)
+                    .collect();
+                if let Some(inner_shape) = convex_hull_from_points(points) {
+                    return round_shape(&inner_shape, distance * (1.0 - shrink));
+                }
+            }
+        }
+    }
+    round_shape(shape, border_radius)
+}
+/// The points the convex hull inside a shape rounded by [`round_shape_inside`] had before it was
+/// shrunk.
+pub fn unshrink_convex_points(inner_shape: &dyn Shape, border_radius: Real) -> Vec<Vector> {
+    let Some((points, center, distance)) = convex_inner_distance(inner_shape) else {
+        return Vec::new();
+    };
+    if distance <= 0.0 {
+        return points.to_vec();
+    }
+    let grow = (distance + border_radius) / distance;
+    points
+        .iter()
+        .map(|point| center + (*point - center) * grow)
+        .collect()
+}
+/// The shape inside a rounded shape and its border radius, `None` for any other shape.
+pub fn unround_shape(shape: &SharedShape) -> Option<(SharedShape, Real)> {
+    match shape.shape_type() {
+        ShapeType::RoundCuboid => shape
+            .as_round_cuboid()
+            .map(|round| (SharedShape::new(round.inner_shape), round.border_radius)),
+        ShapeType::RoundTriangle => shape
+            .as_round_triangle()
+            .map(|round| (SharedShape::new(round.inner_shape), round.border_radius)),
+        #[cfg(feature = "dim2")]
+        ShapeType::RoundConvexPolygon => shape.as_round_convex_polygon().map(|round| {
+            (
+                SharedShape::new(round.inner_shape.clone()),
+                round.border_radius,
+            )
+        }),
+        #[cfg(feature = "dim3")]
+        ShapeType::RoundConvexPolyhedron => shape.as_round_convex_polyhedron().map(|round| {
+            (
+                SharedShape::new(round.inner_shape.clone()),
+                round.border_radius,
+            )
+        }),
+        #[cfg(feature = "dim3")]
+        ShapeType::RoundCylinder => shape
+            .as_round_cylinder()
+            .map(|round| (SharedShape::new(round.inner_shape), round.border_radius)),
+        #[cfg(feature = "dim3")]
+        ShapeType::RoundCone => shape
+            .as_round_cone()
+            .map(|round| (SharedShape::new(round.inner_shape), round.border_radius)),
+        _ => None,
+    }
+}
 pub fn scale_shape(shape: &SharedShape, shape_info: ShapeInfo) -> SharedShape {
+    // The inner shape takes the scale and skew, and the border the smallest scale factor so it
+    // stays round.
+    if let Some((inner_shape, border_radius)) = unround_shape(shape) {
+        let border_scale = shape_info.scale.abs().min_element();
+        return round_shape(
+            &scale_shape(&inner_shape, shape_info),
+            border_radius * border_scale,
+        );
+    }
     let shape = skew_shape(&shape.clone(), shape_info);
     let scale = shape_info.scale;
     if (scale - Vector::ONE).length_squared().is_zero_approx() {
@@ -133,7 +520,7 @@ pub fn scale_shape(shape: &SharedShape, shape_info: ShapeInfo) -> SharedShape {
         #[cfg(feature = "dim3")]
         ShapeType::TriMesh => {
             if let Some(new_shape) = shape.as_trimesh() {
-                return SharedShape::new(new_shape.clone().scaled(scale));
+                return SharedShape::new(scale_trimesh(new_shape, scale));
             }
         }
         #[cfg(feature = "dim3")]
@@ -151,6 +538,35 @@ pub fn scale_shape(shape: &SharedShape, shape_info: ShapeInfo) -> SharedShape {
                 }
             }
         }
+        #[cfg(feature = "dim3")]
+        ShapeType::Cone => {
+            if let Some(new_shape) = shape.as_cone()
+                && let Some(new_shape) = new_shape.scaled(
+                    scale,
+                    crate::servers::rapier_project_settings::motion_settings()
+                        .shape_scale_subdivisions,
+                )
+            {
+                match new_shape {
+                    Left(shape) => return SharedShape::new(shape),
+                    Right(shape) => return SharedShape::new(shape),
+                }
+            }
+        }
+        ShapeType::Triangle => {
+            if let Some(new_shape) = shape.as_triangle() {
+                return SharedShape::triangle(
+                    new_shape.a * scale,
+                    new_shape.b * scale,
+                    new_shape.c * scale,
+                );
+            }
+        }
+        ShapeType::Segment => {
+            if let Some(new_shape) = shape.as_segment() {
+                return SharedShape::segment(new_shape.a * scale, new_shape.b * scale);
+            }
+        }
         #[cfg(feature = "dim2")]
         ShapeType::ConvexPolygon => {
             if let Some(new_shape) = shape.as_convex_polygon()
@@ -167,7 +583,6 @@ pub fn scale_shape(shape: &SharedShape, shape_info: ShapeInfo) -> SharedShape {
                 return SharedShape::new(new_shape);
             }
         }
-        #[cfg(feature = "dim3")]
         ShapeType::HeightField => {
             if let Some(new_shape) = shape.as_heightfield() {
                 let new_shape = new_shape.clone().scaled(scale);
@@ -192,13 +607,28 @@ pub fn scale_shape(shape: &SharedShape, shape_info: ShapeInfo) -> SharedShape {
             if let Some(new_shape) = shape.as_compound() {
                 let new_shapes = new_shape.shapes();
                 let mut shapes_vec = Vec::new();
+                // The parts of a skewed shape are already skewed.
+                let mut part_info = shape_info;
+                #[cfg(feature = "dim2")]
+                {
+                    part_info.skew = 0.0;
+                }
+                #[cfg(feature = "dim3")]
+                {
+                    part_info.skew = Mat3::IDENTITY;
+                }
                 for shape in new_shapes {
-                    let new_shape = scale_shape(&shape.1, shape_info);
+                    let new_shape = scale_shape(&shape.1, part_info);
                     shapes_vec.push((shape.0, new_shape));
                 }
                 return SharedShape::compound(shapes_vec);
             }
         }
+        ShapeType::Voxels => {
+            if let Some(new_shape) = shape.as_voxels() {
+                return SharedShape::new(new_shape.clone().scaled(scale.abs()));
+            }
+        }
         ShapeType::Custom => {
             if let Some(new_shape) = shape.scale_dyn(
                 scale,
@@ -435,14 +865,21 @@ impl PhysicsEngine {
         }
     }
 
+    /// Colliders of dynamic bodies use the convex parts of concave shapes when decomposition is
+    /// enabled for them.
     pub fn collider_set_transform(
         &mut self,
         world_handle: WorldHandle,
         collider_handle: ColliderHandle,
         shape_info: ShapeInfo,
+        dynamic: bool,
     ) {
-        if let Some(shape) = self.get_shape(shape_info.handle) {
-            let new_shape = scale_shape(shape, shape_info);
+        let shape = dynamic
+            .then(|| self.shape_get_convex_parts(shape_info.handle))
+            .flatten()
+            .or_else(|| self.get_shape(shape_info.handle).cloned());
+        if let Some(shape) = shape {
+            let new_shape = scale_shape(&shape, shape_info);
             if let Some(physics_world) = self.get_mut_world(world_handle)
                 && let Some(collider) = physics_world
                     .physics_objects
@@ -459,6 +896,28 @@ impl PhysicsEngine {
         }
     }
 
+    /// Edits the collider's own copy of a voxel shape, so the collider is not recreated.
+    /// Returns `false` when the collider's shape is not plain voxels, so it has to be rebuilt.
+    pub fn collider_set_voxel(
+        &mut self,
+        world_handle: WorldHandle,
+        collider_handle: ColliderHandle,
+        cell: IVector,
+        filled: bool,
+    ) -> bool {
+        if let Some(physics_world) = self.get_mut_world(world_handle)
+            && let Some(collider) = physics_world
+                .physics_objects
+                .collider_set
+                .get_mut(collider_handle)
+            && let Some(voxels) = collider.shape_mut().as_voxels_mut()
+        {
+            voxels.set_voxel(cell, filled);
+            return true;
+        }
+        false
+    }
+
     /// A `threshold` of `0.0` reports every contact.
     pub fn collider_set_contact_force_events(
         &mut self,
@@ -488,3 +947,78 @@ impl PhysicsEngine {
         }
     }
 }
+#[cfg(all(test, feature = "dim3"))]
+mod tests {
+    use super::*;
+    const WORLD: WorldHandle = 1;
+    const VOXELS: ShapeHandle = 2;
+    const BALL: ShapeHandle = 3;
+    fn create_world(physics_engine: &mut PhysicsEngine) {
+        let settings = WorldSettings {
+            particle_radius: 1.0,
+            smoothing_factor: 1.0,
+            counters_enabled: false,
+            boundary_coef: 1.0,
+            #[cfg(feature = "parallel")]
+            thread_count: 1,
+        };
+        physics_engine.world_create(&settings, WORLD);
+    }
+    fn create_collider(
+        physics_engine: &mut PhysicsEngine,
+        shape_handle: ShapeHandle,
+    ) -> ColliderHandle {
+        let body_handle = physics_engine.body_create(
+            WORLD,
+            Vector::ZERO,
+            Rotation::IDENTITY,
+            BodyType::Static,
+            0.0,
+            0.0,
+            0.0,
+        );
+        physics_engine.collider_create_solid(
+            WORLD,
+            shape_handle,
+            &Material::new(1, 1, 0),
+            body_handle,
+            &UserData::invalid_user_data(),
+        )
+    }
+    fn collider_voxel_count(
+        physics_engine: &PhysicsEngine,
+        collider_handle: ColliderHandle,
+    ) -> usize {
+        physics_engine
+            .get_world(WORLD)
+            .unwrap()
+            .physics_objects
+            .collider_set
+            .get(collider_handle)
+            .unwrap()
+            .shape()
+            .as_voxels()
+            .unwrap()
+            .voxels()
+            .count()
+    }
+    #[test]
+    fn voxel_edit_applies_to_voxel_colliders_only() {
+        let mut physics_engine = PhysicsEngine::default();
+        create_world(&mut physics_engine);
+        physics_engine.shape_create_voxels(Vector::ONE, &[IVector::ZERO], VOXELS);
+        physics_engine.shape_create_circle(0.5, BALL);
+        let voxel_collider = create_collider(&mut physics_engine, VOXELS);
+        let ball_collider = create_collider(&mut physics_engine, BALL);
+        let cell = IVector::new(1, 0, 0);
+        assert!(physics_engine.collider_set_voxel(WORLD, voxel_collider, cell, true));
+        assert_eq!(collider_voxel_count(&physics_engine, voxel_collider), 2);
+        // The collider edits its own copy, the shape is edited separately.
+        assert_eq!(physics_engine.shape_get_voxels(VOXELS).1.len(), 1);
+        assert!(physics_engine.collider_set_voxel(WORLD, voxel_collider, cell, false));
+        assert_eq!(collider_voxel_count(&physics_engine, voxel_collider), 1);
+        // Anything else has to be rebuilt by the caller.
+        assert!(!physics_engine.collider_set_voxel(WORLD, ball_collider, cell, true));
+        assert!(!physics_engine.collider_set_voxel(WORLD, ColliderHandle::invalid(), cell, true));
+    }
+}
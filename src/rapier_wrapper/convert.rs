@@ -1,5 +1,6 @@
 #[cfg(feature = "dim3")]
 use rapier::prelude::AngVector;
+use rapier::prelude::IVector;
 #[cfg(feature = "dim2")]
 use rapier::prelude::Real;
 use rapier::prelude::Vector;
@@ -24,6 +25,22 @@ pub fn vector_to_godot<T: Into<Vector>>(vec: T) -> godot::builtin::Vector2 {
     crate::Vector2::new(vec.x, vec.y)
 }
 #[cfg(feature = "dim3")]
+pub fn ivector_to_rapier(vec: crate::Vector3i) -> IVector {
+    IVector::new(vec.x as _, vec.y as _, vec.z as _)
+}
+#[cfg(feature = "dim2")]
+pub fn ivector_to_rapier(vec: crate::Vector2i) -> IVector {
+    IVector::new(vec.x as _, vec.y as _)
+}
+#[cfg(feature = "dim3")]
+pub fn ivector_to_godot(vec: IVector) -> godot::builtin::Vector3i {
+    crate::Vector3i::new(vec.x as _, vec.y as _, vec.z as _)
+}
+#[cfg(feature = "dim2")]
+pub fn ivector_to_godot(vec: IVector) -> godot::builtin::Vector2i {
+    crate::Vector2i::new(vec.x as _, vec.y as _)
+}
+#[cfg(feature = "dim3")]
 pub fn angle_to_rapier(angle: Angle) -> AngVector {
     vector_to_rapier(angle)
 }
@@ -1,4 +1,6 @@
 use godot::global::godot_error;
+#[cfg(feature = "dim3")]
+use rapier::na::ComplexField;
 use rapier::prelude::*;
 
 use super::ANG_ZERO;
@@ -6,6 +8,74 @@ use crate::joints::rapier_joint_base::RapierJointType;
 #[cfg(feature = "dim3")]
 use crate::rapier_wrapper::joint::glamx::Quat;
 use crate::rapier_wrapper::prelude::*;
+/// Share of a coupling's position error corrected on each step.
+const COUPLING_ERP: Real = 0.2;
+/// Position errors below this are left alone, so coupled bodies at rest can fall asleep.
+const COUPLING_POSITION_EPSILON: Real = 1.0e-4;
+/// Velocity errors below this get no impulse, so the coupling does not wake the bodies.
+const COUPLING_VELOCITY_EPSILON: Real = 1.0e-4;
+#[derive(Copy, Clone, Debug)]
+pub struct InverseKinematicsTarget {
+    pub joint_handle: JointHandle,
+    pub target_transform: Pose,
+    /// Relative to the other targets solved together.
+    pub weight: Real,
+    pub constrained_axes: JointAxesMask,
+}
+/// One side of a [`JointCoupling`]: either the body's angular velocity or its linear velocity,
+/// projected on an axis given in the body's local frame. In 2D the axis of an angular side is
+/// ignored.
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CouplingAxis {
+    pub angular: bool,
+    pub local_axis: Vector,
+}
+/// Keeps how far `body_2` moved along `axis_2` equal to `ratio` times how far `body_1` moved
+/// along `axis_1`, counted from when the coupling is first solved.
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct JointCoupling {
+    pub body_1: RigidBodyHandle,
+    pub body_2: RigidBodyHandle,
+    pub axis_1: CouplingAxis,
+    pub axis_2: CouplingAxis,
+    pub ratio: Real,
+    /// Starts over from the current poses when missing, as after an import.
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pub tracking: Option<CouplingTracking>,
+}
+/// How far the bodies of a [`JointCoupling`] moved along their axes, accumulated step by step
+/// from their poses so that whole turns count.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CouplingTracking {
+    pub pose_1: Pose,
+    pub pose_2: Pose,
+    pub position_1: Real,
+    pub position_2: Real,
+}
+/// World frames of a joint and the state of each of its axes, in [`JointAxis`] order.
+pub struct JointDebugInfo {
+    pub body_1: RigidBodyHandle,
+    pub body_2: RigidBodyHandle,
+    pub frame_1: Pose,
+    pub frame_2: Pose,
+    /// Offset along linear axes and angle around angular axes, measured the way rapier measures
+    /// them for limits and motors.
+    pub positions: [Real; SPATIAL_DIM],
+    pub locked_axes: JointAxesMask,
+    pub limit_axes: JointAxesMask,
+    pub motor_axes: JointAxesMask,
+    pub limits: [[Real; 2]; SPATIAL_DIM],
+    pub motor_target_positions: [Real; SPATIAL_DIM],
+    pub motor_target_velocities: [Real; SPATIAL_DIM],
+}
 impl PhysicsEngine {
     pub fn get_multibody_rigidbodies(
         &mut self,
@@ -84,6 +154,315 @@ impl PhysicsEngine {
         multibody.apply_displacements(displacements.as_slice());
     }
 
+    /// Solves inverse kinematics for several links of the same multibody at once, each pulled
+    /// towards its own target. Every iteration sums the damped least-squares step of each
+    /// unconverged target, scaled by its share of the total weight, so targets on separate
+    /// branches move independently and shared joints settle on a weighted compromise.
+    /// `options.constrained_axes` is ignored in favour of each target's own mask. The root link
+    /// is never moved, even when its body is dynamic.
+    ///
+    /// Returns the solved generalized coordinates of every link, as
+    /// [`PhysicsEngine::multibody_get_link_positions`] reports them, in link order. They are
+    /// only applied to the multibody when `apply` is set.
+    pub fn multibody_solve_ik_targets(
+        &mut self,
+        world_handle: WorldHandle,
+        targets: &[InverseKinematicsTarget],
+        options: InverseKinematicsOption,
+        apply: bool,
+    ) -> Vec<Vec<Real>> {
+        let Some(first_target) = targets.first() else {
+            return Vec::new();
+        };
+        let Some(physics_world) = self.get_mut_world(world_handle) else {
+            godot_error!(
+                "Failed to solve IK: world handle {:?} is not valid",
+                world_handle
+            );
+            return Vec::new();
+        };
+        let multibody_joint_set = &mut physics_world.physics_objects.multibody_joint_set;
+        let rigid_body_set = &physics_world.physics_objects.rigid_body_set;
+        let mut link_ids = Vec::with_capacity(targets.len());
+        let mut root = None;
+        for target in targets {
+            let Some((multibody, link_id)) =
+                multibody_joint_set.get(MultibodyJointHandle(target.joint_handle.index))
+            else {
+                godot_error!(
+                    "Failed to solve IK: joint handle {:?} is not a valid multibody joint",
+                    target.joint_handle
+                );
+                return Vec::new();
+            };
+            let multibody_root = multibody.root().rigid_body_handle();
+            if root.is_some_and(|root| root != multibody_root) {
+                godot_error!("Failed to solve IK: all targets must belong to the same multibody");
+                return Vec::new();
+            }
+            root = Some(multibody_root);
+            link_ids.push(link_id);
+        }
+        let Some((multibody, _)) =
+            multibody_joint_set.get_mut(MultibodyJointHandle(first_target.joint_handle.index))
+        else {
+            return Vec::new();
+        };
+        let ndofs = multibody.ndofs();
+        if ndofs == 0 {
+            godot_error!("Cannot solve IK: multibody has 0 degrees of freedom");
+            return Vec::new();
+        }
+        let total_weight: Real = targets.iter().map(|target| target.weight.max(0.0)).sum();
+        if total_weight <= 0.0 {
+            godot_error!("Cannot solve IK: target weights add up to 0");
+            return Vec::new();
+        }
+        let branches: Vec<Vec<usize>> = link_ids
+            .iter()
+            .map(|link_id| multibody.kinematic_branch(*link_id))
+            .collect();
+        let root_link = (
+            multibody.root().assembly_id(),
+            multibody.root().joint().ndofs(),
+        );
+        let mut displacements = nalgebra::DVector::zeros(ndofs);
+        let mut jacobian = rapier::math::Jacobian::zeros(0);
+        for _ in 0..options.max_iters {
+            let mut step = nalgebra::DVector::zeros(ndofs);
+            let mut converged = true;
+            for (target, branch) in targets.iter().zip(branches.iter()) {
+                let pose = multibody.forward_kinematics_single_branch(
+                    rigid_body_set,
+                    branch,
+                    Some(displacements.as_slice()),
+                    Some(&mut jacobian),
+                );
+                // Keep the root in place, so a floating base is not dragged towards the targets.
+                jacobian.columns_mut(root_link.0, root_link.1).fill(0.0);
+                let delta = ik_pose_delta(&pose, &target.target_transform, target.constrained_axes);
+                if delta.rows(0, rapier::math::DIM).norm() <= options.epsilon_linear
+                    && delta.rows(rapier::math::DIM, rapier::math::ANG_DIM).norm()
+                        <= options.epsilon_angular
+                {
+                    continue;
+                }
+                converged = false;
+                Multibody::inverse_kinematics_delta_with_jacobian(
+                    &jacobian,
+                    &(delta * (target.weight.max(0.0) / total_weight)),
+                    options.damping,
+                    &mut step,
+                );
+            }
+            if converged {
+                break;
+            }
+            displacements += step;
+        }
+        // Validate displacements are finite before applying
+        if !displacements.iter().all(|&d| d.is_finite()) {
+            godot_error!("IK solver produced non-finite displacements, skipping application");
+            return Vec::new();
+        }
+        let positions = multibody
+            .links()
+            .map(|link| {
+                let mut joint = *link.joint();
+                joint.apply_displacement(&displacements.as_slice()[link.assembly_id()..]);
+                let locked_bits = joint.data.locked_axes.bits();
+                let coords = joint.coords();
+                (0..rapier::math::SPATIAL_DIM)
+                    .filter(|axis| locked_bits & (1 << axis) == 0)
+                    .map(|axis| coords[axis])
+                    .collect()
+            })
+            .collect();
+        if apply {
+            multibody.apply_displacements(displacements.as_slice());
+        }
+        positions
+    }
+
+    /// The multibody a multibody joint belongs to, the id of the link that joint drives, and the
+    /// rigid bodies the multibody moves.
+    fn get_multibody_link_mut(
+        &mut self,
+        world_handle: WorldHandle,
+        joint_handle: JointHandle,
+    ) -> Option<(&mut Multibody, usize, &mut RigidBodySet)> {
+        let Some(physics_world) = self.get_mut_world(world_handle) else {
+            godot_error!("World handle {:?} is not valid", world_handle);
+            return None;
+        };
+        let Some((multibody, link_id)) = physics_world
+            .physics_objects
+            .multibody_joint_set
+            .get_mut(MultibodyJointHandle(joint_handle.index))
+        else {
+            godot_error!(
+                "Joint handle {:?} is not a valid multibody joint",
+                joint_handle
+            );
+            return None;
+        };
+        Some((
+            multibody,
+            link_id,
+            &mut physics_world.physics_objects.rigid_body_set,
+        ))
+    }
+
+    /// Generalized coordinates of the link driven by the joint, one per free degree of freedom:
+    /// unlocked linear axes first, then unlocked angular axes. Only joints with at most one free
+    /// angular axis are supported, see [`multibody_link_has_coords`].
+    pub fn multibody_get_link_positions(
+        &mut self,
+        world_handle: WorldHandle,
+        joint_handle: JointHandle,
+    ) -> Vec<Real> {
+        let Some((multibody, link_id, _)) = self.get_multibody_link_mut(world_handle, joint_handle)
+        else {
+            return Vec::new();
+        };
+        let Some(link) = multibody.link(link_id) else {
+            return Vec::new();
+        };
+        let joint = link.joint();
+        if !multibody_link_has_coords(joint) {
+            godot_error!("Joints with more than one free angular axis have no link positions");
+            return Vec::new();
+        }
+        let locked_bits = joint.data.locked_axes.bits();
+        let coords = joint.coords();
+        (0..rapier::math::SPATIAL_DIM)
+            .filter(|axis| locked_bits & (1 << axis) == 0)
+            .map(|axis| coords[axis])
+            .collect()
+    }
+
+    /// Moves the link driven by the joint to the given generalized coordinates, in the order of
+    /// [`PhysicsEngine::multibody_get_link_positions`], and updates the bodies of the whole
+    /// multibody to match. Joints with more than one free angular axis are rejected.
+    pub fn multibody_set_link_positions(
+        &mut self,
+        world_handle: WorldHandle,
+        joint_handle: JointHandle,
+        positions: &[Real],
+    ) {
+        let Some((multibody, link_id, rigid_body_set)) =
+            self.get_multibody_link_mut(world_handle, joint_handle)
+        else {
+            return;
+        };
+        let Some(link) = multibody.link_mut(link_id) else {
+            return;
+        };
+        if !multibody_link_has_coords(&link.joint) {
+            godot_error!("Cannot set the position of a joint with more than one free angular axis");
+            return;
+        }
+        let locked_bits = link.joint.data.locked_axes.bits();
+        let coords = link.joint.coords();
+        let current: Vec<Real> = (0..rapier::math::SPATIAL_DIM)
+            .filter(|axis| locked_bits & (1 << axis) == 0)
+            .map(|axis| coords[axis])
+            .collect();
+        let displacements: Vec<Real> = current
+            .iter()
+            .enumerate()
+            .map(|(dof, current)| positions.get(dof).map_or(0.0, |target| target - current))
+            .collect();
+        link.joint.apply_displacement(&displacements);
+        multibody.forward_kinematics(rigid_body_set, false);
+        multibody.update_rigid_bodies(rigid_body_set, false);
+    }
+
+    /// Generalized velocities of the link driven by the joint, in the order of
+    /// [`PhysicsEngine::multibody_get_link_positions`].
+    pub fn multibody_get_link_velocities(
+        &mut self,
+        world_handle: WorldHandle,
+        joint_handle: JointHandle,
+    ) -> Vec<Real> {
+        let Some((multibody, link_id, _)) = self.get_multibody_link_mut(world_handle, joint_handle)
+        else {
+            return Vec::new();
+        };
+        let Some(link) = multibody.link(link_id) else {
+            return Vec::new();
+        };
+        multibody.joint_velocity(link).iter().copied().collect()
+    }
+
+    pub fn multibody_set_link_velocities(
+        &mut self,
+        world_handle: WorldHandle,
+        joint_handle: JointHandle,
+        velocities: &[Real],
+    ) {
+        let Some((multibody, link_id, _)) = self.get_multibody_link_mut(world_handle, joint_handle)
+        else {
+            return;
+        };
+        let Some(link) = multibody.link(link_id) else {
+            return;
+        };
+        let assembly_id = link.assembly_id();
+        let ndofs = link.joint().ndofs().min(velocities.len());
+        let mut generalized_velocity = multibody.generalized_velocity_mut();
+        for (dof, velocity) in velocities.iter().take(ndofs).enumerate() {
+            generalized_velocity[assembly_id + dof] = *velocity;
+        }
+    }
+
+    /// Changes the generalized velocities of the link driven by the joint by the given
+    /// generalized impulses. Each is scaled by the inverse inertia rapier reports for its degree
+    /// of freedom with the rest of the articulation free to move, which is exact for a single
+    /// free link and an approximation of the coupled response otherwise.
+    pub fn multibody_apply_link_impulses(
+        &mut self,
+        world_handle: WorldHandle,
+        joint_handle: JointHandle,
+        impulses: &[Real],
+    ) {
+        let Some((multibody, link_id, rigid_body_set)) =
+            self.get_multibody_link_mut(world_handle, joint_handle)
+        else {
+            return;
+        };
+        let Some(link) = multibody.link(link_id) else {
+            return;
+        };
+        let assembly_id = link.assembly_id();
+        let ndofs = link.joint().ndofs().min(impulses.len());
+        let inverse_inertia = multibody.dof_inverse_inertia(rigid_body_set);
+        let mut generalized_velocity = multibody.generalized_velocity_mut();
+        for (dof, impulse) in impulses.iter().take(ndofs).enumerate() {
+            generalized_velocity[assembly_id + dof] += inverse_inertia[assembly_id + dof] * impulse;
+        }
+    }
+
+    pub fn multibody_set_self_contacts_enabled(
+        &mut self,
+        world_handle: WorldHandle,
+        joint_handle: JointHandle,
+        enabled: bool,
+    ) {
+        if let Some((multibody, _, _)) = self.get_multibody_link_mut(world_handle, joint_handle) {
+            multibody.set_self_contacts_enabled(enabled);
+        }
+    }
+
+    pub fn multibody_is_self_contacts_enabled(
+        &mut self,
+        world_handle: WorldHandle,
+        joint_handle: JointHandle,
+    ) -> bool {
+        self.get_multibody_link_mut(world_handle, joint_handle)
+            .is_some_and(|(multibody, _, _)| multibody.self_contacts_enabled())
+    }
+
     fn joint_wake_up_connected_rigidbodies(
         &mut self,
         world_handle: WorldHandle,
@@ -126,6 +505,7 @@ impl PhysicsEngine {
         motor_position_enabled: bool,
         motor_max_force: Real,
         disable_collision: bool,
+        user_data: &UserData,
     ) -> JointHandle {
         self.body_wake_up(world_handle, body_handle_1, false);
         self.body_wake_up(world_handle, body_handle_2, false);
@@ -144,7 +524,13 @@ impl PhysicsEngine {
             } else if motor_position_enabled {
                 joint = joint.motor_position(motor_target_position, motor_stiffness, motor_damping)
             }
-            return physics_world.insert_joint(body_handle_1, body_handle_2, joint_type, joint);
+            return physics_world.insert_joint(
+                body_handle_1,
+                body_handle_2,
+                joint_type,
+                joint,
+                user_data,
+            );
         }
         JointHandle::default()
     }
@@ -172,6 +558,7 @@ impl PhysicsEngine {
         motor_position_enabled: bool,
         motor_max_force: Real,
         disable_collision: bool,
+        user_data: &UserData,
     ) -> JointHandle {
         self.body_wake_up(world_handle, body_handle_1, false);
         self.body_wake_up(world_handle, body_handle_2, false);
@@ -213,6 +600,7 @@ impl PhysicsEngine {
                 body_handle_2,
                 joint_type,
                 joint.build(),
+                user_data,
             );
         }
         JointHandle::default()
@@ -229,6 +617,7 @@ impl PhysicsEngine {
         anchor_2: Vector,
         joint_type: RapierJointType,
         disable_collision: bool,
+        user_data: &UserData,
     ) -> JointHandle {
         self.body_wake_up(world_handle, body_handle_1, false);
         self.body_wake_up(world_handle, body_handle_2, false);
@@ -237,7 +626,13 @@ impl PhysicsEngine {
                 .local_anchor1(anchor_1)
                 .local_anchor2(anchor_2)
                 .contacts_enabled(!disable_collision);
-            return physics_world.insert_joint(body_handle_1, body_handle_2, joint_type, joint);
+            return physics_world.insert_joint(
+                body_handle_1,
+                body_handle_2,
+                joint_type,
+                joint,
+                user_data,
+            );
         }
         JointHandle::default()
     }
@@ -257,6 +652,7 @@ impl PhysicsEngine {
         linear_limit_lower: f32,
         joint_type: RapierJointType,
         disable_collision: bool,
+        user_data: &UserData,
     ) -> JointHandle {
         self.body_wake_up(world_handle, body_handle_1, false);
         self.body_wake_up(world_handle, body_handle_2, false);
@@ -274,6 +670,7 @@ impl PhysicsEngine {
                 body_handle_2,
                 joint_type,
                 joint.build(),
+                user_data,
             );
         }
         JointHandle::default()
@@ -398,6 +795,7 @@ impl PhysicsEngine {
         limits: Vector,
         joint_type: RapierJointType,
         disable_collision: bool,
+        user_data: &UserData,
     ) -> JointHandle {
         self.body_wake_up(world_handle, body_handle_1, false);
         self.body_wake_up(world_handle, body_handle_2, false);
@@ -407,7 +805,13 @@ impl PhysicsEngine {
                 .local_anchor2(anchor_2)
                 .limits([limits.x, limits.y])
                 .contacts_enabled(!disable_collision);
-            return physics_world.insert_joint(body_handle_1, body_handle_2, joint_type, joint);
+            return physics_world.insert_joint(
+                body_handle_1,
+                body_handle_2,
+                joint_type,
+                joint,
+                user_data,
+            );
         }
         JointHandle::default()
     }
@@ -423,6 +827,7 @@ impl PhysicsEngine {
         max_distance: Real,
         joint_type: RapierJointType,
         disable_collision: bool,
+        user_data: &UserData,
     ) -> JointHandle {
         self.body_wake_up(world_handle, body_handle_1, false);
         self.body_wake_up(world_handle, body_handle_2, false);
@@ -431,7 +836,13 @@ impl PhysicsEngine {
                 .local_anchor1(anchor_1)
                 .local_anchor2(anchor_2)
                 .contacts_enabled(!disable_collision);
-            return physics_world.insert_joint(body_handle_1, body_handle_2, joint_type, joint);
+            return physics_world.insert_joint(
+                body_handle_1,
+                body_handle_2,
+                joint_type,
+                joint,
+                user_data,
+            );
         }
         JointHandle::default()
     }
@@ -446,6 +857,7 @@ impl PhysicsEngine {
         anchor_2: Vector,
         joint_type: RapierJointType,
         disable_collision: bool,
+        user_data: &UserData,
     ) -> JointHandle {
         self.body_wake_up(world_handle, body_handle_1, false);
         self.body_wake_up(world_handle, body_handle_2, false);
@@ -454,13 +866,18 @@ impl PhysicsEngine {
                 .local_anchor1(anchor_1)
                 .local_anchor2(anchor_2)
                 .contacts_enabled(!disable_collision);
-            return physics_world.insert_joint(body_handle_1, body_handle_2, joint_type, joint);
+            return physics_world.insert_joint(
+                body_handle_1,
+                body_handle_2,
+                joint_type,
+                joint,
+                user_data,
+            );
         }
         JointHandle::default()
     }
 
     #[allow(clippy::too_many_arguments)]
-    #[cfg(feature = "dim2")]
     pub fn joint_create_spring(
         &mut self,
         world_handle: WorldHandle,
@@ -473,6 +890,7 @@ impl PhysicsEngine {
         rest_length: Real,
         joint_type: RapierJointType,
         disable_collision: bool,
+        user_data: &UserData,
     ) -> JointHandle {
         self.body_wake_up(world_handle, body_handle_1, false);
         self.body_wake_up(world_handle, body_handle_2, false);
@@ -482,12 +900,17 @@ impl PhysicsEngine {
                 .local_anchor1(anchor_1)
                 .local_anchor2(anchor_2)
                 .contacts_enabled(!disable_collision);
-            return physics_world.insert_joint(body_handle_1, body_handle_2, joint_type, joint);
+            return physics_world.insert_joint(
+                body_handle_1,
+                body_handle_2,
+                joint_type,
+                joint,
+                user_data,
+            );
         }
         JointHandle::default()
     }
 
-    #[cfg(feature = "dim2")]
     pub fn joint_change_spring_params(
         &mut self,
         world_handle: WorldHandle,
@@ -505,6 +928,71 @@ impl PhysicsEngine {
         }
     }
 
+    /// Drives every angular axis of the joint with a position motor so that the rotation of the
+    /// second joint frame relative to the first one reaches `target_rotation`. Zero stiffness and
+    /// damping remove the angular motors.
+    #[cfg(feature = "dim3")]
+    pub fn joint_set_target_rotation(
+        &mut self,
+        world_handle: WorldHandle,
+        joint_handle: JointHandle,
+        target_rotation: Rotation,
+        stiffness: Real,
+        damping: Real,
+    ) {
+        self.joint_wake_up_connected_rigidbodies(world_handle, joint_handle);
+        if let Some(physics_world) = self.get_mut_world(world_handle)
+            && let Some(joint) = physics_world.get_mut_joint(joint_handle)
+        {
+            if stiffness == 0.0 && damping == 0.0 {
+                joint.motor_axes.remove(JointAxesMask::ANG_AXES);
+                return;
+            }
+            // Rapier measures the angle of each axis as twice the arcsine of the matching
+            // quaternion component, with the quaternion kept in the positive hemisphere.
+            let target_rotation = if target_rotation.w < 0.0 {
+                -target_rotation
+            } else {
+                target_rotation
+            };
+            let imag = target_rotation.xyz();
+            for (axis, component) in [
+                (JointAxis::AngX, imag.x),
+                (JointAxis::AngY, imag.y),
+                (JointAxis::AngZ, imag.z),
+            ] {
+                let angle = ComplexField::asin(component.clamp(-1.0, 1.0)) * 2.0;
+                joint.set_motor_position(axis, angle, stiffness, damping);
+            }
+        }
+    }
+
+    /// Bounds the distance kept by a spring joint, passing `None` for both removes the bounds.
+    pub fn joint_set_spring_limits(
+        &mut self,
+        world_handle: WorldHandle,
+        joint_handle: JointHandle,
+        min_length: Option<Real>,
+        max_length: Option<Real>,
+    ) {
+        self.joint_wake_up_connected_rigidbodies(world_handle, joint_handle);
+        if let Some(physics_world) = self.get_mut_world(world_handle)
+            && let Some(joint) = physics_world.get_mut_joint(joint_handle)
+        {
+            if min_length.is_none() && max_length.is_none() {
+                joint.limit_axes.remove(JointAxesMask::LIN_X);
+            } else {
+                joint.set_limits(
+                    JointAxis::LinX,
+                    [
+                        min_length.unwrap_or(0.0),
+                        max_length.unwrap_or(Real::INFINITY),
+                    ],
+                );
+            }
+        }
+    }
+
     /// World-space impulse an impulse joint applied to body 2 over the last step. Multibody
     /// joints resolve their constraints in reduced coordinates and report zero.
     pub fn joint_set_softness(
@@ -525,6 +1013,27 @@ impl PhysicsEngine {
         }
     }
 
+    /// An impulse joint leaving every axis free. It constrains nothing, but links the bodies in
+    /// rapier for joints that are solved outside of it.
+    pub fn joint_create_free(
+        &mut self,
+        world_handle: WorldHandle,
+        body_handle_1: RigidBodyHandle,
+        body_handle_2: RigidBodyHandle,
+        user_data: &UserData,
+    ) -> JointHandle {
+        if let Some(physics_world) = self.get_mut_world(world_handle) {
+            return physics_world.insert_joint(
+                body_handle_1,
+                body_handle_2,
+                RapierJointType::Impulse,
+                GenericJoint::default(),
+                user_data,
+            );
+        }
+        JointHandle::default()
+    }
+
     pub fn joint_set_enabled(
         &mut self,
         world_handle: WorldHandle,
@@ -552,10 +1061,12 @@ impl PhysicsEngine {
                 .get(joint.body1())
         {
             let frame_rotation = body1.position().rotation * joint.data.local_frame1.rotation;
+            let substeps = physics_world.solver_substeps as Real;
             let axis_impulse = |axis: usize| {
-                joint.impulses[axis]
+                (joint.impulses[axis]
                     + joint.data.limits[axis].impulse
-                    + joint.data.motors[axis].impulse
+                    + joint.data.motors[axis].impulse)
+                    * substeps
             };
             #[cfg(feature = "dim2")]
             {
@@ -618,7 +1129,13 @@ impl PhysicsEngine {
         }
         // Insert the joint into the new set
         if let Some(physics_world) = self.get_mut_world(world_handle) {
-            physics_world.insert_joint(body1, body2, new_joint_type, joint_data)
+            physics_world.insert_joint(
+                body1,
+                body2,
+                new_joint_type,
+                joint_data,
+                &UserData::new(joint_data.user_data),
+            )
         } else {
             godot_error!("Invalid joint data");
             JointHandle::default()
@@ -652,6 +1169,7 @@ impl PhysicsEngine {
         axis_2: Rotation,
         joint_type: RapierJointType,
         disable_collision: bool,
+        user_data: &UserData,
     ) -> JointHandle {
         self.body_wake_up(world_handle, body_handle_1, false);
         self.body_wake_up(world_handle, body_handle_2, false);
@@ -677,7 +1195,13 @@ impl PhysicsEngine {
                 .local_frame1(pose_1)
                 .local_frame2(pose_2)
                 .contacts_enabled(!disable_collision);
-            return physics_world.insert_joint(body_handle_1, body_handle_2, joint_type, joint);
+            return physics_world.insert_joint(
+                body_handle_1,
+                body_handle_2,
+                joint_type,
+                joint,
+                user_data,
+            );
         }
         JointHandle::default()
     }
@@ -697,6 +1221,7 @@ impl PhysicsEngine {
         twist_span: Real,
         joint_type: RapierJointType,
         disable_collision: bool,
+        user_data: &UserData,
     ) -> JointHandle {
         self.body_wake_up(world_handle, body_handle_1, false);
         self.body_wake_up(world_handle, body_handle_2, false);
@@ -720,7 +1245,13 @@ impl PhysicsEngine {
                 .limits(JointAxis::AngY, [-swing_span, swing_span])
                 .limits(JointAxis::AngZ, [-swing_span, swing_span])
                 .contacts_enabled(!disable_collision);
-            return physics_world.insert_joint(body_handle_1, body_handle_2, joint_type, joint);
+            return physics_world.insert_joint(
+                body_handle_1,
+                body_handle_2,
+                joint_type,
+                joint,
+                user_data,
+            );
         }
         JointHandle::default()
     }
@@ -791,4 +1322,816 @@ impl PhysicsEngine {
             }
         }
     }
+
+    /// Sets the velocities of the coupled bodies before rapier steps the world, so contacts
+    /// and the other joints are solved on top of them. Rapier's coupled axes only tie the axes
+    /// of one joint together, so a ratio between two bodies is solved here as its own velocity
+    /// constraint, with part of the drift in position fed back as a bias, by Gauss-Seidel over
+    /// all couplings in the order given. Couplings whose joint is disabled, as broken joints
+    /// are, are skipped and start over from the current poses once enabled again.
+    pub fn joint_solve_couplings(
+        &mut self,
+        world_handle: WorldHandle,
+        couplings: &mut [(JointHandle, &mut JointCoupling)],
+        dt: Real,
+        iterations: usize,
+    ) {
+        let Some(physics_world) = self.get_mut_world(world_handle) else {
+            return;
+        };
+        let enabled: Vec<bool> = couplings
+            .iter()
+            .map(|(joint_handle, _)| {
+                physics_world
+                    .get_joint(*joint_handle)
+                    .is_some_and(|joint| joint.is_enabled())
+            })
+            .collect();
+        let rigid_body_set = &mut physics_world.physics_objects.rigid_body_set;
+        for ((_, coupling), enabled) in couplings.iter_mut().zip(enabled.iter()) {
+            if *enabled {
+                coupling_track(rigid_body_set, coupling);
+            } else {
+                coupling.tracking = None;
+            }
+        }
+        for _ in 0..iterations {
+            for (_, coupling) in couplings.iter() {
+                coupling_solve_velocity(rigid_body_set, coupling, dt);
+            }
+        }
+    }
+
+    /// World frames of the joint on each body and where each of its axes stands, with the
+    /// limits and motor targets set on them. `None` if the joint does not exist.
+    pub fn joint_get_debug_info(
+        &self,
+        world_handle: WorldHandle,
+        joint_handle: JointHandle,
+    ) -> Option<JointDebugInfo> {
+        let physics_world = self.get_world(world_handle)?;
+        let joint = physics_world.get_joint(joint_handle)?;
+        let (body_1, body_2) = match joint_handle.joint_type {
+            RapierJointType::Impulse => {
+                let joint = physics_world.get_impulse_joint(joint_handle)?;
+                (joint.body1(), joint.body2())
+            }
+            RapierJointType::MultiBody | RapierJointType::MultiBodyKinematic => {
+                let (multibody, link_id) = physics_world
+                    .physics_objects
+                    .multibody_joint_set
+                    .get(MultibodyJointHandle(joint_handle.index))?;
+                let link = multibody.link(link_id)?;
+                let parent = multibody.link(link.parent_id()?)?;
+                (parent.rigid_body_handle(), link.rigid_body_handle())
+            }
+        };
+        let rigid_body_set = &physics_world.physics_objects.rigid_body_set;
+        let frame_1 = *rigid_body_set.get(body_1)?.position() * joint.local_frame1;
+        let frame_2 = *rigid_body_set.get(body_2)?.position() * joint.local_frame2;
+        let relative = frame_1.inverse() * frame_2;
+        let mut positions = [0.0; SPATIAL_DIM];
+        for (axis, position) in positions.iter_mut().enumerate().take(DIM) {
+            *position = relative.translation[axis];
+        }
+        #[cfg(feature = "dim2")]
+        {
+            positions[DIM] = relative.rotation.angle();
+        }
+        #[cfg(feature = "dim3")]
+        {
+            let rotation = if relative.rotation.w < 0.0 {
+                -relative.rotation
+            } else {
+                relative.rotation
+            };
+            for (axis, component) in rotation.xyz().to_array().into_iter().enumerate() {
+                positions[DIM + axis] = ComplexField::asin(component.clamp(-1.0, 1.0)) * 2.0;
+            }
+        }
+        Some(JointDebugInfo {
+            body_1,
+            body_2,
+            frame_1,
+            frame_2,
+            positions,
+            locked_axes: joint.locked_axes,
+            limit_axes: joint.limit_axes,
+            motor_axes: joint.motor_axes,
+            limits: joint.limits.map(|limits| [limits.min, limits.max]),
+            motor_target_positions: joint.motors.map(|motor| motor.target_pos),
+            motor_target_velocities: joint.motors.map(|motor| motor.target_vel),
+        })
+    }
+}
+/// Whether rapier keeps a coordinate for every free axis of the joint. It tracks the angle
+/// around a single free angular axis, but with two free angular axes it cannot move the joint
+/// at all, and with three it only keeps a rotation, the coordinates adding up angular
+/// velocities. Fixed, prismatic and revolute joints, and generic ones locking at least two
+/// angular axes, are supported; spherical joints are not.
+pub fn multibody_link_has_coords(joint: &MultibodyJoint) -> bool {
+    let locked_angular_bits = joint.data.locked_axes.bits() >> rapier::math::DIM;
+    rapier::math::ANG_DIM - locked_angular_bits.count_ones() as usize <= 1
+}
+fn coupling_world_axis(body: &RigidBody, axis: &CouplingAxis) -> Vector {
+    (*body.rotation() * axis.local_axis).normalize_or_zero()
+}
+/// How far `pose` moved from `previous` along `axis`, or around it for an angular axis.
+fn coupling_axis_motion(previous: &Pose, pose: &Pose, axis: &CouplingAxis) -> Real {
+    let world_axis = (pose.rotation * axis.local_axis).normalize_or_zero();
+    if !axis.angular {
+        return (pose.translation - previous.translation).dot(world_axis);
+    }
+    let delta = pose.rotation * previous.rotation.inverse();
+    #[cfg(feature = "dim2")]
+    {
+        let _ = world_axis;
+        delta.angle()
+    }
+    #[cfg(feature = "dim3")]
+    {
+        let delta = if delta.w < 0.0 { -delta } else { delta };
+        delta.xyz().dot(world_axis).atan2(delta.w) * 2.0
+    }
+}
+fn coupling_track(rigid_body_set: &RigidBodySet, coupling: &mut JointCoupling) {
+    let (Some(body_1), Some(body_2)) = (
+        rigid_body_set.get(coupling.body_1),
+        rigid_body_set.get(coupling.body_2),
+    ) else {
+        return;
+    };
+    let (pose_1, pose_2) = (*body_1.position(), *body_2.position());
+    let tracking = coupling.tracking.get_or_insert(CouplingTracking {
+        pose_1,
+        pose_2,
+        position_1: 0.0,
+        position_2: 0.0,
+    });
+    tracking.position_1 += coupling_axis_motion(&tracking.pose_1, &pose_1, &coupling.axis_1);
+    tracking.position_2 += coupling_axis_motion(&tracking.pose_2, &pose_2, &coupling.axis_2);
+    tracking.pose_1 = pose_1;
+    tracking.pose_2 = pose_2;
+}
+fn coupling_solve_velocity(rigid_body_set: &mut RigidBodySet, coupling: &JointCoupling, dt: Real) {
+    let Some(tracking) = coupling.tracking else {
+        return;
+    };
+    let (Some(body_1), Some(body_2)) = (
+        rigid_body_set.get(coupling.body_1),
+        rigid_body_set.get(coupling.body_2),
+    ) else {
+        return;
+    };
+    let axis_1 = coupling_world_axis(body_1, &coupling.axis_1);
+    let axis_2 = coupling_world_axis(body_2, &coupling.axis_2);
+    let inv_mass =
+        coupling.ratio * coupling.ratio * coupling_inv_mass(body_1, &coupling.axis_1, axis_1)
+            + coupling_inv_mass(body_2, &coupling.axis_2, axis_2);
+    if inv_mass <= Real::EPSILON || dt <= 0.0 {
+        return;
+    }
+    let position_error = coupling.ratio * tracking.position_1 - tracking.position_2;
+    let bias = if position_error.abs() > COUPLING_POSITION_EPSILON {
+        position_error * COUPLING_ERP / dt
+    } else {
+        0.0
+    };
+    let error = coupling.ratio * coupling_velocity(body_1, &coupling.axis_1, axis_1)
+        - coupling_velocity(body_2, &coupling.axis_2, axis_2)
+        + bias;
+    if error.abs() <= COUPLING_VELOCITY_EPSILON {
+        return;
+    }
+    let impulse = -error / inv_mass;
+    if let Some(body_1) = rigid_body_set.get_mut(coupling.body_1) {
+        coupling_apply_impulse(body_1, &coupling.axis_1, axis_1, impulse * coupling.ratio);
+    }
+    if let Some(body_2) = rigid_body_set.get_mut(coupling.body_2) {
+        coupling_apply_impulse(body_2, &coupling.axis_2, axis_2, -impulse);
+    }
+}
+fn coupling_velocity(body: &RigidBody, axis: &CouplingAxis, world_axis: Vector) -> Real {
+    if !axis.angular {
+        return body.linvel().dot(world_axis);
+    }
+    #[cfg(feature = "dim2")]
+    {
+        let _ = world_axis;
+        body.angvel()
+    }
+    #[cfg(feature = "dim3")]
+    {
+        body.angvel().dot(world_axis)
+    }
+}
+fn coupling_inv_mass(body: &RigidBody, axis: &CouplingAxis, world_axis: Vector) -> Real {
+    if !body.is_dynamic() {
+        return 0.0;
+    }
+    let mass_properties = body.mass_properties();
+    if !axis.angular {
+        return (mass_properties.effective_inv_mass * world_axis).dot(world_axis);
+    }
+    #[cfg(feature = "dim2")]
+    {
+        let _ = world_axis;
+        mass_properties.effective_world_inv_inertia
+    }
+    #[cfg(feature = "dim3")]
+    {
+        (mass_properties.effective_world_inv_inertia * world_axis).dot(world_axis)
+    }
+}
+fn coupling_apply_impulse(
+    body: &mut RigidBody,
+    axis: &CouplingAxis,
+    world_axis: Vector,
+    impulse: Real,
+) {
+    if !body.is_dynamic() {
+        return;
+    }
+    if !axis.angular {
+        body.apply_impulse(world_axis * impulse, true);
+        return;
+    }
+    #[cfg(feature = "dim2")]
+    body.apply_torque_impulse(impulse, true);
+    #[cfg(feature = "dim3")]
+    body.apply_torque_impulse(world_axis * impulse, true);
+}
+/// Movement taking `pose` to `target`, linear part first, with the axes outside
+/// `constrained_axes` zeroed, as rapier's own IK solver measures it.
+fn ik_pose_delta(
+    pose: &Pose,
+    target: &Pose,
+    constrained_axes: JointAxesMask,
+) -> nalgebra::SVector<Real, { rapier::math::SPATIAL_DIM }> {
+    let delta_lin = target.translation - pose.translation;
+    #[cfg(feature = "dim2")]
+    let mut delta = {
+        let delta_ang = (target.rotation * pose.rotation.inverse()).angle();
+        nalgebra::vector![delta_lin.x, delta_lin.y, delta_ang]
+    };
+    #[cfg(feature = "dim3")]
+    let mut delta = {
+        let delta_ang = (target.rotation * pose.rotation.inverse()).to_scaled_axis();
+        nalgebra::vector![
+            delta_lin.x,
+            delta_lin.y,
+            delta_lin.z,
+            delta_ang.x,
+            delta_ang.y,
+            delta_ang.z
+        ]
+    };
+    for axis in 0..rapier::math::SPATIAL_DIM {
+        if constrained_axes.bits() & (1 << axis) == 0 {
+            delta[axis] = 0.0;
+        }
+    }
+    delta
+}
+#[cfg(all(test, feature = "dim3"))]
+mod tests {
+    use super::*;
+    use godot::prelude::Rid;
+
+    use crate::joints::rapier_joint_base::RapierJointBase;
+    use crate::spaces::rapier_space_state::RapierSpaceState;
+    const WORLD: WorldHandle = 1;
+    const BALL: ShapeHandle = 2;
+    const DT: Real = 1.0 / 60.0;
+    fn create_world(physics_engine: &mut PhysicsEngine) -> RapierSpaceState {
+        let settings = WorldSettings {
+            particle_radius: 1.0,
+            smoothing_factor: 1.0,
+            counters_enabled: false,
+            boundary_coef: 1.0,
+            #[cfg(feature = "parallel")]
+            thread_count: 1,
+        };
+        physics_engine.shape_create_circle(0.5, BALL);
+        RapierSpaceState::new(WORLD, physics_engine, &settings)
+    }
+    /// A ball of mass 1 whose collisions are filtered out, so only its joints act on it.
+    fn create_body(
+        physics_engine: &mut PhysicsEngine,
+        body_type: BodyType,
+        pos: Vector,
+    ) -> RigidBodyHandle {
+        let body_handle = physics_engine.body_create(
+            WORLD,
+            pos,
+            Rotation::IDENTITY,
+            body_type,
+            0.0,
+            0.0,
+            Real::MAX,
+        );
+        physics_engine.collider_create_solid(
+            WORLD,
+            BALL,
+            &Material::new(0, 0, 0),
+            body_handle,
+            &UserData::invalid_user_data(),
+        );
+        physics_engine.body_set_mass_properties(
+            WORLD,
+            body_handle,
+            1.0,
+            AngVector::splat(0.1),
+            Vector::ZERO,
+            true,
+            false,
+        );
+        body_handle
+    }
+    fn step_world(physics_engine: &mut PhysicsEngine, gravity: Vector) {
+        let physics_world = physics_engine.get_mut_world(WORLD).unwrap();
+        let physics_objects = &mut physics_world.physics_objects;
+        physics_world.physics_pipeline.step(
+            gravity,
+            &IntegrationParameters {
+                dt: DT,
+                ..Default::default()
+            },
+            &mut physics_objects.island_manager,
+            &mut physics_objects.broad_phase,
+            &mut physics_objects.narrow_phase,
+            &mut physics_objects.rigid_body_set,
+            &mut physics_objects.collider_set,
+            &mut physics_objects.impulse_joint_set,
+            &mut physics_objects.multibody_joint_set,
+            &mut physics_objects.ccd_solver,
+            &(),
+            &(),
+        );
+    }
+    fn body_position(physics_engine: &PhysicsEngine, body_handle: RigidBodyHandle) -> Vector {
+        physics_engine
+            .get_world(WORLD)
+            .unwrap()
+            .physics_objects
+            .rigid_body_set
+            .get(body_handle)
+            .unwrap()
+            .translation()
+    }
+    #[test]
+    fn joint_breaks_past_its_threshold_and_is_reported() {
+        let mut physics_engine = PhysicsEngine::default();
+        let mut state = create_world(&mut physics_engine);
+        let gravity = Vector::new(0.0, -10.0, 0.0);
+        let anchor = create_body(&mut physics_engine, BodyType::Static, Vector::ZERO);
+        let load = create_body(
+            &mut physics_engine,
+            BodyType::Dynamic,
+            Vector::new(0.0, -2.0, 0.0),
+        );
+        let handle = physics_engine.joint_create_spherical(
+            WORLD,
+            anchor,
+            load,
+            Vector::new(0.0, -2.0, 0.0),
+            Vector::ZERO,
+            RapierJointType::Impulse,
+            true,
+            &UserData::invalid_user_data(),
+        );
+        let joint_id = 7;
+        let mut joint = RapierJointBase::new(
+            joint_id,
+            Rid::Invalid,
+            WORLD,
+            WORLD,
+            handle,
+            RapierJointType::Impulse,
+        );
+        // Holding a mass of 1 against gravity takes an impulse of 10 * DT every step.
+        joint.set_break_threshold(0.2, Real::INFINITY, &mut physics_engine);
+        assert!(joint.is_breakable());
+        for _ in 0..10 {
+            step_world(&mut physics_engine, gravity);
+        }
+        assert!((body_position(&physics_engine, load).y + 2.0).abs() < 1.0e-2);
+        let (impulse, _) = physics_engine.joint_get_reaction_impulse(WORLD, handle);
+        assert!((impulse.length() - 10.0 * DT).abs() < 0.02);
+        assert!(!joint.exceeds_break_threshold(impulse.length(), 0.0));
+        // Lowering the threshold below the load breaks the joint the way the space does.
+        joint.set_break_threshold(0.1, Real::INFINITY, &mut physics_engine);
+        assert!(joint.exceeds_break_threshold(impulse.length(), 0.0));
+        physics_engine.joint_set_enabled(WORLD, handle, false);
+        joint.set_broken(true);
+        state.joint_add_to_broken_list(joint_id);
+        assert!(!joint.is_breakable());
+        assert_eq!(state.take_broken_joint_list(), vec![joint_id]);
+        for _ in 0..10 {
+            step_world(&mut physics_engine, gravity);
+        }
+        assert!(body_position(&physics_engine, load).y < -2.1);
+        // Setting a threshold again re-arms and re-enables it.
+        joint.set_break_threshold(0.2, Real::INFINITY, &mut physics_engine);
+        assert!(joint.is_breakable());
+        assert!(
+            physics_engine
+                .get_world(WORLD)
+                .unwrap()
+                .get_joint(handle)
+                .unwrap()
+                .is_enabled()
+        );
+    }
+    #[test]
+    fn multibody_link_positions_need_at_most_one_free_angular_axis() {
+        let mut physics_engine = PhysicsEngine::default();
+        let _state = create_world(&mut physics_engine);
+        let root = create_body(&mut physics_engine, BodyType::Static, Vector::ZERO);
+        let arm = create_body(
+            &mut physics_engine,
+            BodyType::Dynamic,
+            Vector::new(0.0, -1.0, 0.0),
+        );
+        let hand = create_body(
+            &mut physics_engine,
+            BodyType::Dynamic,
+            Vector::new(0.0, -2.0, 0.0),
+        );
+        let hinge = physics_engine.joint_create_revolute(
+            WORLD,
+            root,
+            arm,
+            Vector::ZERO,
+            Vector::new(0.0, 1.0, 0.0),
+            Rotation::IDENTITY,
+            Rotation::IDENTITY,
+            0.0,
+            0.0,
+            false,
+            0.0,
+            false,
+            RapierJointType::MultiBody,
+            0.0,
+            0.0,
+            0.0,
+            false,
+            0.0,
+            true,
+            &UserData::invalid_user_data(),
+        );
+        let ball = physics_engine.joint_create_spherical(
+            WORLD,
+            arm,
+            hand,
+            Vector::new(0.0, -0.5, 0.0),
+            Vector::new(0.0, 0.5, 0.0),
+            RapierJointType::MultiBody,
+            true,
+            &UserData::invalid_user_data(),
+        );
+        let link_has_coords = |physics_engine: &PhysicsEngine, joint_handle: JointHandle| {
+            let multibody_joint_set = &physics_engine
+                .get_world(WORLD)
+                .unwrap()
+                .physics_objects
+                .multibody_joint_set;
+            let (multibody, link_id) = multibody_joint_set
+                .get(MultibodyJointHandle(joint_handle.index))
+                .unwrap();
+            multibody_link_has_coords(multibody.link(link_id).unwrap().joint())
+        };
+        assert!(link_has_coords(&physics_engine, hinge));
+        assert!(!link_has_coords(&physics_engine, ball));
+        physics_engine.multibody_set_link_positions(WORLD, hinge, &[0.5]);
+        let positions = physics_engine.multibody_get_link_positions(WORLD, hinge);
+        assert_eq!(positions.len(), 1);
+        assert!((positions[0] - 0.5).abs() < 1.0e-5);
+    }
+    #[test]
+    fn ik_targets_move_the_links_but_not_a_floating_root() {
+        let mut physics_engine = PhysicsEngine::default();
+        let _state = create_world(&mut physics_engine);
+        let root = create_body(&mut physics_engine, BodyType::Dynamic, Vector::ZERO);
+        let arm = create_body(
+            &mut physics_engine,
+            BodyType::Dynamic,
+            Vector::new(0.0, -1.0, 0.0),
+        );
+        let hinge = physics_engine.joint_create_revolute(
+            WORLD,
+            root,
+            arm,
+            Vector::ZERO,
+            Vector::new(0.0, 1.0, 0.0),
+            Rotation::IDENTITY,
+            Rotation::IDENTITY,
+            0.0,
+            0.0,
+            false,
+            0.0,
+            false,
+            RapierJointType::MultiBody,
+            0.0,
+            0.0,
+            0.0,
+            false,
+            0.0,
+            true,
+            &UserData::invalid_user_data(),
+        );
+        let target = InverseKinematicsTarget {
+            joint_handle: hinge,
+            target_transform: Pose::from_parts(
+                Vector::new(0.5.sin(), -0.5.cos(), 0.0),
+                Rotation::IDENTITY,
+            ),
+            weight: 1.0,
+            constrained_axes: JointAxesMask::LIN_AXES,
+        };
+        let current = physics_engine.multibody_solve_ik_targets(
+            WORLD,
+            &[target],
+            InverseKinematicsOption {
+                max_iters: 0,
+                ..Default::default()
+            },
+            false,
+        );
+        let solved = physics_engine.multibody_solve_ik_targets(
+            WORLD,
+            &[target],
+            InverseKinematicsOption::default(),
+            false,
+        );
+        assert_eq!(solved.len(), 2);
+        assert_eq!(solved[0], current[0]);
+        assert_eq!(solved[1].len(), 1);
+        assert!((solved[1][0].abs() - 0.5).abs() < 1.0e-2);
+    }
+    #[test]
+    fn gear_ratio_holds_after_one_step_and_pauses_while_disabled() {
+        let mut physics_engine = PhysicsEngine::default();
+        let _state = create_world(&mut physics_engine);
+        let body_1 = create_body(&mut physics_engine, BodyType::Dynamic, Vector::ZERO);
+        let body_2 = create_body(
+            &mut physics_engine,
+            BodyType::Dynamic,
+            Vector::new(2.0, 0.0, 0.0),
+        );
+        let handle =
+            physics_engine.joint_create_free(WORLD, body_1, body_2, &UserData::invalid_user_data());
+        let axis = CouplingAxis {
+            angular: true,
+            local_axis: Vector::Z,
+        };
+        let mut coupling = JointCoupling {
+            body_1,
+            body_2,
+            axis_1: axis,
+            axis_2: axis,
+            ratio: -2.0,
+            tracking: None,
+        };
+        // Rapier computes the world inertia of the bodies on their first step.
+        step_world(&mut physics_engine, Vector::ZERO);
+        let angvel_z = |physics_engine: &PhysicsEngine, body_handle: RigidBodyHandle| {
+            physics_engine
+                .get_world(WORLD)
+                .unwrap()
+                .physics_objects
+                .rigid_body_set
+                .get(body_handle)
+                .unwrap()
+                .angvel()
+                .z
+        };
+        physics_engine
+            .get_mut_world(WORLD)
+            .unwrap()
+            .physics_objects
+            .rigid_body_set
+            .get_mut(body_1)
+            .unwrap()
+            .set_angvel(Vector::new(0.0, 0.0, 1.0), true);
+        physics_engine.joint_solve_couplings(WORLD, &mut [(handle, &mut coupling)], DT, 4);
+        step_world(&mut physics_engine, Vector::ZERO);
+        let (angvel_1, angvel_2) = (
+            angvel_z(&physics_engine, body_1),
+            angvel_z(&physics_engine, body_2),
+        );
+        assert!(angvel_1 > 0.0);
+        assert!((angvel_2 + 2.0 * angvel_1).abs() < 1.0e-4);
+        // Bodies moving with the ratio need no impulse.
+        physics_engine.joint_solve_couplings(WORLD, &mut [(handle, &mut coupling)], DT, 4);
+        assert_eq!(angvel_z(&physics_engine, body_1), angvel_1);
+        assert_eq!(angvel_z(&physics_engine, body_2), angvel_2);
+        // A disabled coupling leaves the bodies alone and starts over once enabled.
+        physics_engine.joint_set_enabled(WORLD, handle, false);
+        physics_engine
+            .get_mut_world(WORLD)
+            .unwrap()
+            .physics_objects
+            .rigid_body_set
+            .get_mut(body_2)
+            .unwrap()
+            .set_angvel(Vector::ZERO, true);
+        physics_engine.joint_solve_couplings(WORLD, &mut [(handle, &mut coupling)], DT, 4);
+        assert_eq!(angvel_z(&physics_engine, body_2), 0.0);
+        assert!(coupling.tracking.is_none());
+    }
+    #[test]
+    fn spring_limits_bound_the_stretch_until_removed() {
+        let mut physics_engine = PhysicsEngine::default();
+        let _state = create_world(&mut physics_engine);
+        let gravity = Vector::new(0.0, -10.0, 0.0);
+        let anchor = create_body(&mut physics_engine, BodyType::Static, Vector::ZERO);
+        let load = create_body(
+            &mut physics_engine,
+            BodyType::Dynamic,
+            Vector::new(0.0, -1.0, 0.0),
+        );
+        // Holding a mass of 1 against gravity stretches it by 0.5 past its rest length.
+        let handle = physics_engine.joint_create_spring(
+            WORLD,
+            anchor,
+            load,
+            Vector::ZERO,
+            Vector::ZERO,
+            20.0,
+            5.0,
+            1.0,
+            RapierJointType::Impulse,
+            true,
+            &UserData::invalid_user_data(),
+        );
+        physics_engine.joint_set_spring_limits(WORLD, handle, None, Some(1.2));
+        for _ in 0..300 {
+            step_world(&mut physics_engine, gravity);
+        }
+        let length = body_position(&physics_engine, load).length();
+        assert!((length - 1.2).abs() < 0.02);
+        physics_engine.joint_set_spring_limits(WORLD, handle, None, None);
+        for _ in 0..300 {
+            step_world(&mut physics_engine, gravity);
+        }
+        let length = body_position(&physics_engine, load).length();
+        assert!((length - 1.5).abs() < 0.02);
+    }
+    #[test]
+    fn target_rotation_turns_the_second_body_until_removed() {
+        let mut physics_engine = PhysicsEngine::default();
+        let _state = create_world(&mut physics_engine);
+        let anchor = create_body(&mut physics_engine, BodyType::Static, Vector::ZERO);
+        let head = create_body(&mut physics_engine, BodyType::Dynamic, Vector::ZERO);
+        let handle = physics_engine.joint_create_spherical(
+            WORLD,
+            anchor,
+            head,
+            Vector::ZERO,
+            Vector::ZERO,
+            RapierJointType::Impulse,
+            true,
+            &UserData::invalid_user_data(),
+        );
+        // A turn past half a revolution is reached the short way round.
+        let target = Rotation::from_axis_angle(Vector::new(1.0, 1.0, 0.0).normalize(), 4.0);
+        physics_engine.joint_set_target_rotation(WORLD, handle, target, 50.0, 10.0);
+        for _ in 0..300 {
+            step_world(&mut physics_engine, Vector::ZERO);
+        }
+        let rotation = *physics_engine
+            .get_world(WORLD)
+            .unwrap()
+            .physics_objects
+            .rigid_body_set
+            .get(head)
+            .unwrap()
+            .rotation();
+        assert!(rotation.angle_between(target) < 0.02);
+        physics_engine.joint_set_target_rotation(WORLD, handle, target, 0.0, 0.0);
+        let joint = physics_engine
+            .get_world(WORLD)
+            .unwrap()
+            .get_joint(handle)
+            .unwrap();
+        assert!(joint.motor_axes.is_empty());
+    }
+    #[test]
+    fn attached_joints_carry_the_joint_id_given_at_creation() {
+        let mut physics_engine = PhysicsEngine::default();
+        let _state = create_world(&mut physics_engine);
+        let anchor = create_body(&mut physics_engine, BodyType::Static, Vector::ZERO);
+        let arm = create_body(
+            &mut physics_engine,
+            BodyType::Dynamic,
+            Vector::new(0.0, -1.0, 0.0),
+        );
+        let hand = create_body(
+            &mut physics_engine,
+            BodyType::Dynamic,
+            Vector::new(0.0, -2.0, 0.0),
+        );
+        let shoulder = physics_engine.joint_create_spherical(
+            WORLD,
+            anchor,
+            arm,
+            Vector::new(0.0, -0.5, 0.0),
+            Vector::new(0.0, 0.5, 0.0),
+            RapierJointType::Impulse,
+            true,
+            &RapierJointBase::user_data(5),
+        );
+        let wrist = physics_engine.joint_create_fixed(
+            WORLD,
+            arm,
+            hand,
+            Vector::new(0.0, -0.5, 0.0),
+            Vector::new(0.0, 0.5, 0.0),
+            RapierJointType::MultiBody,
+            true,
+            &RapierJointBase::user_data(6),
+        );
+        let mut attached = physics_engine.body_get_attached_joints(WORLD, arm);
+        attached.sort_by_key(|(_, _, user_data)| user_data.part1);
+        assert_eq!(attached.len(), 2);
+        assert_eq!(attached[0].0, shoulder);
+        assert_eq!(attached[0].1, anchor);
+        assert_eq!(attached[0].2.part1, 5);
+        assert_eq!(attached[1].0.index, wrist.index);
+        assert_eq!(attached[1].1, hand);
+        assert_eq!(attached[1].2.part1, 6);
+        // Moving a joint to the other joint set keeps its id.
+        let shoulder = physics_engine.recreate_joint(WORLD, shoulder, RapierJointType::MultiBody);
+        let attached = physics_engine.body_get_attached_joints(WORLD, anchor);
+        assert_eq!(attached.len(), 1);
+        assert_eq!(attached[0].0.index, shoulder.index);
+        assert_eq!(attached[0].2.part1, 5);
+    }
+    #[test]
+    fn debug_info_reports_frames_and_hinge_state() {
+        let mut physics_engine = PhysicsEngine::default();
+        let _state = create_world(&mut physics_engine);
+        let anchor = create_body(&mut physics_engine, BodyType::Static, Vector::ZERO);
+        let wheel = create_body(
+            &mut physics_engine,
+            BodyType::Dynamic,
+            Vector::new(0.0, -1.0, 0.0),
+        );
+        let hinge = physics_engine.joint_create_revolute(
+            WORLD,
+            anchor,
+            wheel,
+            Vector::new(0.0, -1.0, 0.0),
+            Vector::ZERO,
+            Rotation::IDENTITY,
+            Rotation::IDENTITY,
+            -0.5,
+            0.5,
+            true,
+            2.0,
+            true,
+            RapierJointType::Impulse,
+            0.0,
+            0.0,
+            0.0,
+            false,
+            1.0,
+            true,
+            &UserData::invalid_user_data(),
+        );
+        physics_engine
+            .get_mut_world(WORLD)
+            .unwrap()
+            .physics_objects
+            .rigid_body_set
+            .get_mut(wheel)
+            .unwrap()
+            .set_position(
+                Pose::from_parts(
+                    Vector::new(0.0, -1.0, 0.0),
+                    Rotation::from_axis_angle(Vector::Z, 0.3),
+                ),
+                true,
+            );
+        let info = physics_engine.joint_get_debug_info(WORLD, hinge).unwrap();
+        assert_eq!((info.body_1, info.body_2), (anchor, wheel));
+        assert!((info.frame_1.translation - Vector::new(0.0, -1.0, 0.0)).length() < 1.0e-5);
+        assert!((info.frame_2.translation - info.frame_1.translation).length() < 1.0e-5);
+        // The hinge turns around rapier's X axis, the other axes stay locked at 0.
+        assert!((info.positions[3].abs() - 0.3).abs() < 1.0e-4);
+        for axis in [0, 1, 2, 4, 5] {
+            assert!(info.positions[axis].abs() < 1.0e-4);
+        }
+        assert_eq!(info.locked_axes, JointAxesMask::LOCKED_REVOLUTE_AXES);
+        assert_eq!(info.limit_axes, JointAxesMask::ANG_X);
+        assert_eq!(info.motor_axes, JointAxesMask::ANG_X);
+        assert_eq!(info.limits[3], [-0.5, 0.5]);
+        assert_eq!(info.motor_target_velocities[3], 2.0);
+        assert!(
+            physics_engine
+                .joint_get_debug_info(WORLD, JointHandle::default())
+                .is_none()
+        );
+    }
 }
@@ -1,11 +1,15 @@
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::sync::Weak;
 use std::sync::mpsc;
 
 use godot::global::godot_error;
 use hashbrown::HashMap;
 use rapier::data::Index;
+use rapier::parry::shape::Shape;
 use rapier::parry::utils::PoseOpt;
 use rapier::prelude::*;
 use salva::integrations::rapier::FluidsPipeline;
@@ -195,6 +199,9 @@ pub struct PhysicsWorld {
     pub physics_objects: PhysicsObjects,
     pub physics_pipeline: PhysicsPipeline,
     pub fluids_pipeline: FluidsPipeline,
+    /// Substeps the last step was split into. Rapier keeps the joint impulses of the last
+    /// substep only, so they are scaled by it to get the impulse of the whole step.
+    pub solver_substeps: usize,
     #[cfg(feature = "parallel")]
     pub thread_pool: &'static rapier::rayon::ThreadPool,
 }
@@ -227,6 +234,7 @@ impl PhysicsWorld {
                 settings.smoothing_factor,
                 settings.boundary_coef,
             ),
+            solver_substeps: IntegrationParameters::default().num_solver_iterations,
             #[cfg(feature = "parallel")]
             thread_pool: shared_thread_pool(settings.thread_count),
         }
@@ -262,6 +270,7 @@ impl PhysicsWorld {
             integration_parameters.num_solver_iterations = iterations.into();
         }
         integration_parameters.num_internal_pgs_iterations = settings.num_internal_pgs_iterations;
+        self.solver_substeps = integration_parameters.num_solver_iterations;
         let gravity = settings.pixel_gravity;
         let liquid_gravity = settings.pixel_liquid_gravity;
         let physics_hooks = PhysicsHooksCollisionFilter {
@@ -559,7 +568,10 @@ impl PhysicsWorld {
         body_handle_2: RigidBodyHandle,
         joint_type: RapierJointType,
         joint: impl Into<GenericJoint>,
+        user_data: &UserData,
     ) -> JointHandle {
+        let mut joint = joint.into();
+        joint.user_data = user_data.get_data();
         let rigid_body_1_handle = body_handle_1;
         let rigid_body_2_handle = body_handle_2;
         match joint_type {
@@ -718,6 +730,24 @@ impl PhysicsWorld {
 pub struct PhysicsEngine {
     pub physics_worlds: HashMap<RapierId, PhysicsWorld>,
     pub shapes: HashMap<RapierId, SharedShape>,
+    /// Per-shape override of the convex decomposition project setting.
+    pub convex_decomposition_overrides: HashMap<RapierId, bool>,
+    /// Decompositions computed so far, dropped whenever their shape is replaced.
+    pub convex_decompositions: HashMap<RapierId, SharedShape>,
+    /// Per-shape override of the trimesh flags project setting.
+    #[cfg(feature = "dim3")]
+    pub trimesh_flags_overrides: HashMap<RapierId, TriMeshFlags>,
+    /// Border radius of the shapes created rounded.
+    pub border_radii: HashMap<RapierId, Real>,
+    /// What the primitive shapes were built from, so custom shapes can describe them again.
+    #[cfg(feature = "dim3")]
+    pub primitives: HashMap<RapierId, PrimitiveShape>,
+    /// Scaled copies of shapes made by queries, dropped whenever their shape changes. Queries
+    /// only borrow the engine, hence the lock.
+    pub scaled_shapes: Mutex<HashMap<RapierId, Vec<(ScaleKey, SharedShape)>>>,
+    /// Shapes worth sharing between handles by the hash of their content, so identical
+    /// geometry is stored once.
+    pub shape_contents: HashMap<u64, Vec<Weak<dyn Shape>>>,
 }
 impl PhysicsEngine {
     pub fn get_mut_world(&mut self, world_handle: WorldHandle) -> Option<&mut PhysicsWorld> {
@@ -729,11 +759,27 @@ impl PhysicsEngine {
     }
 
     pub fn insert_shape(&mut self, shape: SharedShape, handle: ShapeHandle) {
-        self.shapes.insert(handle, shape);
+        let shape = self.share_shape_content(shape);
+        if let Some(replaced) = self.shapes.insert(handle, shape) {
+            self.forget_shape_content(replaced);
+        }
+        self.convex_decompositions.remove(&handle);
+        self.forget_scaled_shapes(handle);
     }
 
     pub fn remove_shape(&mut self, shape_handle: ShapeHandle) {
-        self.shapes.remove_entry(&shape_handle);
+        if let Some(shape) = self.shapes.remove(&shape_handle) {
+            self.forget_shape_content(shape);
+        }
+        self.convex_decompositions.remove(&shape_handle);
+        self.forget_scaled_shapes(shape_handle);
+    }
+
+    pub fn forget_scaled_shapes(&mut self, shape_handle: ShapeHandle) {
+        self.scaled_shapes
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&shape_handle);
     }
 
     pub fn get_shape(&self, shape_handle: ShapeHandle) -> Option<&SharedShape> {
@@ -2,6 +2,8 @@ use std::ops::Mul;
 
 use godot::global::godot_error;
 use godot::global::godot_warn;
+use rapier::parry::query::ClosestPoints;
+use rapier::parry::query::NonlinearRigidMotion;
 use rapier::parry::query::QueryDispatcher;
 use rapier::parry::query::ShapeCastOptions;
 use rapier::parry::query::ShapeCastStatus;
@@ -85,6 +87,52 @@ pub struct ContactResult {
     pub normal1: Vector,
     pub normal2: Vector,
 }
+#[derive(Copy, Clone, Default, Debug)]
+pub struct PenetrationInfo {
+    pub collider: ColliderHandle,
+    pub user_data: UserData,
+    pub local_user_data: UserData,
+    pub pixel_point: Vector,
+    pub pixel_collider_point: Vector,
+    /// Points out of the other collider, which is the direction that resolves the overlap.
+    pub normal: Vector,
+    /// Positive when the shapes overlap, negative when they are only within the margin.
+    pub depth: Real,
+}
+#[derive(Copy, Clone, Default, Debug)]
+pub struct TrajectoryHit {
+    pub collider: ColliderHandle,
+    pub user_data: UserData,
+    pub pixel_position: Vector,
+    pub normal: Vector,
+    /// Seconds since the start of the trajectory.
+    pub time: Real,
+}
+#[derive(Clone, Default, Debug)]
+pub struct TrajectoryCastResult {
+    /// Shape origins along the path: the start, the end of every segment and every impact.
+    pub pixel_points: Vec<Vector>,
+    pub hits: Vec<TrajectoryHit>,
+    /// Seconds simulated before the path ran out of time or bounces.
+    pub time: Real,
+    /// Whether the path was cut short by `MAX_TRAJECTORY_SEGMENTS` before reaching the
+    /// requested time.
+    pub truncated: bool,
+}
+#[derive(Copy, Clone, Default, Debug)]
+pub struct ExplosionHit {
+    pub collider: ColliderHandle,
+    pub user_data: UserData,
+    /// Point of the collider closest to the explosion center, or the center itself when it is
+    /// inside the collider.
+    pub pixel_point: Vector,
+    pub pixel_distance: Real,
+}
+/// Duration of each linear segment the parabola is split into for casting.
+const TRAJECTORY_SEGMENT_TIME: Real = 1.0 / 60.0;
+/// Most segments one trajectory is cast with, about a minute of flight, so a huge `max_time`
+/// cannot stall the query.
+const MAX_TRAJECTORY_SEGMENTS: usize = 3600;
 /// Floor for the distance parry is asked to look ahead by. Parry reports a contact only
 /// strictly closer than this, so it has to stay above zero for shapes that rest exactly
 /// touching to be seen at all. Callers still compare against their own margin.
@@ -93,6 +141,143 @@ const MIN_CONTACT_PREDICTION: Real = 0.002;
 fn contact_prediction(margin: Real) -> Real {
     Real::max(MIN_CONTACT_PREDICTION, margin)
 }
+/// Closest points of the two shapes however far apart they are, in world space. Overlapping or
+/// touching shapes get their contact, found with a prediction small enough to only visit the
+/// overlapping parts of composite shapes. Separated ones get parry's closest points, which
+/// descend composite shapes best-first instead of visiting every part.
+fn closest_contact(
+    pos1: &Pose,
+    shape1: &dyn Shape,
+    pos2: &Pose,
+    shape2: &dyn Shape,
+) -> Option<rapier::parry::query::Contact> {
+    let pos12 = pos1.inv_mul(pos2);
+    let dispatcher = separation_ray_query_dispatcher();
+    let mut contact = match dispatcher.contact(&pos12, shape1, shape2, MIN_CONTACT_PREDICTION) {
+        Ok(Some(contact)) => contact,
+        Ok(None) => match dispatcher.closest_points(&pos12, shape1, shape2, Real::MAX) {
+            Ok(ClosestPoints::WithinMargin(point1, point2)) => {
+                let offset = pos12 * point2 - point1;
+                let dist = offset.length();
+                let normal1 = offset / dist;
+                rapier::parry::query::Contact::new(
+                    point1,
+                    point2,
+                    normal1,
+                    pos12.inverse_transform_vector(-normal1),
+                    dist,
+                )
+            }
+            Ok(_) => return None,
+            Err(err) => {
+                godot_error!("Shape Distance Error: {:?}", err);
+                return None;
+            }
+        },
+        Err(err) => {
+            godot_error!("Shape Distance Error: {:?}", err);
+            return None;
+        }
+    };
+    contact.transform_by_mut(pos1, pos2);
+    Some(contact)
+}
+fn distance_result(contact: &rapier::parry::query::Contact) -> ContactResult {
+    ContactResult {
+        collided: true,
+        within_margin: contact.dist > 0.0,
+        pixel_distance: contact.dist,
+        pixel_point1: contact.point1,
+        pixel_point2: contact.point2,
+        normal1: contact.normal1,
+        normal2: contact.normal2,
+    }
+}
+/// Body of [`PhysicsEngine::explosion_query`], with the exclusion callback already turned into a
+/// collider predicate.
+fn explosion_hits(
+    physics_world: &RapierWrapperPhysicsWorld,
+    center: Vector,
+    radius: Real,
+    occlusion: bool,
+    predicate: &dyn Fn(ColliderHandle, &Collider) -> bool,
+) -> Vec<ExplosionHit> {
+    let mut filter = QueryFilter::new().exclude_sensors();
+    filter.predicate = Some(predicate);
+    let mut closest: std::collections::HashMap<RigidBodyHandle, ExplosionHit> =
+        std::collections::HashMap::new();
+    let query_aabb = Aabb::new(
+        center - Vector::splat(radius),
+        center + Vector::splat(radius),
+    );
+    for (collider_handle, collider) in physics_world
+        .physics_objects
+        .broad_phase
+        .as_query_pipeline(
+            physics_world
+                .physics_objects
+                .narrow_phase
+                .query_dispatcher(),
+            &physics_world.physics_objects.rigid_body_set,
+            &physics_world.physics_objects.collider_set,
+            filter,
+        )
+        .intersect_aabb_conservative(query_aabb)
+    {
+        let Some(body_handle) = collider.parent() else {
+            continue;
+        };
+        let projection = collider
+            .shape()
+            .project_point(collider.position(), center, true);
+        let distance = projection.point.distance(center);
+        if distance > radius
+            || closest
+                .get(&body_handle)
+                .is_some_and(|hit| hit.pixel_distance <= distance)
+        {
+            continue;
+        }
+        closest.insert(
+            body_handle,
+            ExplosionHit {
+                collider: collider_handle,
+                user_data: physics_world.get_collider_user_data(collider_handle),
+                pixel_point: projection.point,
+                pixel_distance: distance,
+            },
+        );
+    }
+    let mut hits: Vec<(RigidBodyHandle, ExplosionHit)> = closest.into_iter().collect();
+    if occlusion {
+        hits.retain(|(body_handle, hit)| {
+            if hit.pixel_distance <= DEFAULT_EPSILON {
+                return true;
+            }
+            let ray = Ray::new(center, (hit.pixel_point - center) / hit.pixel_distance);
+            let mut occluder_filter = QueryFilter::new()
+                .exclude_sensors()
+                .exclude_rigid_body(*body_handle);
+            occluder_filter.predicate = Some(predicate);
+            physics_world
+                .physics_objects
+                .broad_phase
+                .as_query_pipeline(
+                    physics_world
+                        .physics_objects
+                        .narrow_phase
+                        .query_dispatcher(),
+                    &physics_world.physics_objects.rigid_body_set,
+                    &physics_world.physics_objects.collider_set,
+                    occluder_filter,
+                )
+                .cast_ray(&ray, hit.pixel_distance, true)
+                .is_none()
+        });
+    }
+    hits.sort_by(|(_, a), (_, b)| a.pixel_distance.total_cmp(&b.pixel_distance));
+    hits.into_iter().map(|(_, hit)| hit).collect()
+}
 #[derive(Default)]
 pub struct QueryExcludedInfo {
     pub query_collision_layer_mask: u32,
@@ -203,6 +388,325 @@ impl PhysicsEngine {
         info
     }
 
+    /// Every solid collider the body's colliders overlap or come within `margin` of, measured
+    /// on the colliders as they sit in the world rather than on a shape cast.
+    pub fn body_get_penetrations(
+        &self,
+        world_handle: WorldHandle,
+        body_handle: RigidBodyHandle,
+        margin: Real,
+        max_results: usize,
+    ) -> Vec<PenetrationInfo> {
+        let mut results = Vec::new();
+        if max_results == 0 {
+            return results;
+        }
+        let Some(physics_world) = self.get_world(world_handle) else {
+            return results;
+        };
+        let Some(body) = physics_world
+            .physics_objects
+            .rigid_body_set
+            .get(body_handle)
+        else {
+            return results;
+        };
+        let prediction = contact_prediction(margin);
+        let filter = QueryFilter::new()
+            .exclude_sensors()
+            .exclude_rigid_body(body_handle);
+        for &local_handle in body.colliders() {
+            let Some(local_collider) = physics_world.physics_objects.collider_set.get(local_handle)
+            else {
+                continue;
+            };
+            if local_collider.is_sensor() || !local_collider.is_enabled() {
+                continue;
+            }
+            let query_aabb = local_collider.compute_aabb().loosened(prediction);
+            for (collider_handle, collider) in physics_world
+                .physics_objects
+                .broad_phase
+                .as_query_pipeline(
+                    physics_world
+                        .physics_objects
+                        .narrow_phase
+                        .query_dispatcher(),
+                    &physics_world.physics_objects.rigid_body_set,
+                    &physics_world.physics_objects.collider_set,
+                    filter,
+                )
+                .intersect_aabb_conservative(query_aabb)
+            {
+                let pos12 = local_collider.position().inv_mul(collider.position());
+                match physics_world
+                    .physics_objects
+                    .narrow_phase
+                    .query_dispatcher()
+                    .contact(&pos12, local_collider.shape(), collider.shape(), prediction)
+                {
+                    // Parry only reports a contact strictly closer than the prediction
+                    // distance, so the margin is applied here instead.
+                    Ok(Some(contact)) if contact.dist <= margin => {
+                        results.push(PenetrationInfo {
+                            collider: collider_handle,
+                            user_data: physics_world.get_collider_user_data(collider_handle),
+                            local_user_data: physics_world.get_collider_user_data(local_handle),
+                            pixel_point: local_collider.position() * contact.point1,
+                            pixel_collider_point: collider.position() * contact.point2,
+                            normal: collider.rotation() * contact.normal2,
+                            depth: -contact.dist,
+                        });
+                        if results.len() >= max_results {
+                            return results;
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(err) => godot_error!("contact error: {:?}", err),
+                }
+            }
+        }
+        results
+    }
+
+    /// Earliest time, in seconds, at which the two bodies touch if both keep their current
+    /// linear and angular velocities, searched up to `max_time`. Witnesses and normals are in
+    /// world space at the moment of impact.
+    pub fn bodies_time_of_impact(
+        &self,
+        world_handle: WorldHandle,
+        body_handle_1: RigidBodyHandle,
+        body_handle_2: RigidBodyHandle,
+        max_time: Real,
+    ) -> ShapeCastResult {
+        let mut result = ShapeCastResult::new();
+        result.toi = max_time;
+        result.toi_unsafe = max_time;
+        let Some(physics_world) = self.get_world(world_handle) else {
+            return result;
+        };
+        let rigid_body_set = &physics_world.physics_objects.rigid_body_set;
+        let collider_set = &physics_world.physics_objects.collider_set;
+        let (Some(body1), Some(body2)) = (
+            rigid_body_set.get(body_handle_1),
+            rigid_body_set.get(body_handle_2),
+        ) else {
+            return result;
+        };
+        // Each collider gets its own motion so the shapes can be cast as they sit in the world,
+        // already scaled, while still rotating about their body's center of mass.
+        let collider_motion = |body: &RigidBody, collider: &Collider| {
+            NonlinearRigidMotion::new(
+                *collider.position(),
+                collider
+                    .position()
+                    .inverse_transform_point(body.center_of_mass()),
+                body.linvel(),
+                body.angvel(),
+            )
+        };
+        let is_castable = |collider: &Collider| !collider.is_sensor() && collider.is_enabled();
+        let dispatcher = physics_world
+            .physics_objects
+            .narrow_phase
+            .query_dispatcher();
+        for &collider_handle_1 in body1.colliders() {
+            let Some(collider1) = collider_set.get(collider_handle_1) else {
+                continue;
+            };
+            if !is_castable(collider1) {
+                continue;
+            }
+            let motion1 = collider_motion(body1, collider1);
+            for &collider_handle_2 in body2.colliders() {
+                let Some(collider2) = collider_set.get(collider_handle_2) else {
+                    continue;
+                };
+                if !is_castable(collider2) {
+                    continue;
+                }
+                let motion2 = collider_motion(body2, collider2);
+                match dispatcher.cast_shapes_nonlinear(
+                    &motion1,
+                    collider1.shape(),
+                    &motion2,
+                    collider2.shape(),
+                    0.0,
+                    result.toi,
+                    true,
+                ) {
+                    Ok(Some(hit)) => {
+                        if hit.status == ShapeCastStatus::Failed
+                            || hit.status == ShapeCastStatus::OutOfIterations
+                        {
+                            godot_warn!("time of impact status warn: {:?}", hit.status);
+                        }
+                        if result.collided && hit.time_of_impact >= result.toi {
+                            continue;
+                        }
+                        // parry returns witnesses and normals in each shape's local space at
+                        // the time of impact.
+                        let pos1 = motion1.position_at_time(hit.time_of_impact);
+                        let pos2 = motion2.position_at_time(hit.time_of_impact);
+                        result.collided = true;
+                        result.toi = hit.time_of_impact;
+                        result.toi_unsafe = hit.time_of_impact;
+                        result.pixel_witness1 = pos1 * hit.witness1;
+                        result.pixel_witness2 = pos2 * hit.witness2;
+                        result.normal1 = pos1.rotation * hit.normal1;
+                        result.normal2 = pos2.rotation * hit.normal2;
+                        result.collider = collider_handle_2;
+                        result.user_data = physics_world.get_collider_user_data(collider_handle_2);
+                    }
+                    Ok(None) => {}
+                    Err(err) => godot_error!("time of impact error: {:?}", err),
+                }
+            }
+        }
+        result
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    /// Sweeps the shape along a ballistic path under `gravity`, splitting it into short linear
+    /// segments that are each cast with [`PhysicsEngine::shape_casting`]. On impact the velocity
+    /// is reflected about the hit normal, keeping `bounce` of its normal component, until
+    /// `max_bounces` reflections have been used; the next impact then ends the path. A shape that
+    /// starts overlapping a collider and whose first segment moves further into it hits that
+    /// collider at time zero, which also ends the path. Paths longer than
+    /// `MAX_TRAJECTORY_SEGMENTS` segments stop early and are marked as truncated.
+    pub fn shape_cast_trajectory(
+        &self,
+        world_handle: WorldHandle,
+        shape_info: ShapeInfo,
+        velocity: Vector,
+        gravity: Vector,
+        max_time: Real,
+        max_bounces: usize,
+        bounce: Real,
+        handle_excluded_info: &QueryExcludedInfo,
+        physics_collision_objects: &PhysicsCollisionObjects,
+        physics_ids: &PhysicsIds,
+        space: &RapierSpace,
+    ) -> TrajectoryCastResult {
+        let mut result = TrajectoryCastResult::default();
+        let mut shape_info = shape_info;
+        let mut velocity = velocity;
+        result.pixel_points.push(shape_info.transform.translation);
+        // Casting ignores what the shape already overlaps, so a shape that starts inside
+        // geometry would pass straight through it. Overlaps it is moving out of are left alone,
+        // as rapier leaves resting bodies slightly sunk into what they rest on.
+        let dt = TRAJECTORY_SEGMENT_TIME.min(max_time);
+        let first_motion = velocity * dt + gravity * (0.5 * dt * dt);
+        if let Some(overlap) = self
+            .shape_casting(
+                world_handle,
+                Vector::ZERO,
+                shape_info,
+                0.0,
+                true,
+                false,
+                handle_excluded_info,
+                physics_collision_objects,
+                physics_ids,
+                space,
+                true,
+            )
+            .into_iter()
+            .find(|overlap| first_motion.dot(overlap.normal2) < 0.0)
+        {
+            result.hits.push(TrajectoryHit {
+                collider: overlap.collider,
+                user_data: overlap.user_data,
+                pixel_position: overlap.pixel_witness2,
+                normal: overlap.normal2,
+                time: 0.0,
+            });
+            return result;
+        }
+        for _ in 0..MAX_TRAJECTORY_SEGMENTS {
+            if result.time >= max_time {
+                break;
+            }
+            let dt = TRAJECTORY_SEGMENT_TIME.min(max_time - result.time);
+            let motion = velocity * dt + gravity * (0.5 * dt * dt);
+            let casts = self.shape_casting(
+                world_handle,
+                motion,
+                shape_info,
+                0.0,
+                true,
+                false,
+                handle_excluded_info,
+                physics_collision_objects,
+                physics_ids,
+                space,
+                true,
+            );
+            let Some(cast) = casts.first() else {
+                shape_info.transform.translation += motion;
+                velocity += gravity * dt;
+                result.time += dt;
+                result.pixel_points.push(shape_info.transform.translation);
+                continue;
+            };
+            // The segment is a chord of the parabola, so the fraction travelled along it is
+            // also taken as the fraction of the segment's time.
+            let hit_dt = dt * cast.toi;
+            shape_info.transform.translation += motion * cast.toi;
+            velocity += gravity * hit_dt;
+            result.time += hit_dt;
+            result.pixel_points.push(shape_info.transform.translation);
+            result.hits.push(TrajectoryHit {
+                collider: cast.collider,
+                user_data: cast.user_data,
+                pixel_position: cast.pixel_witness2,
+                normal: cast.normal2,
+                time: result.time,
+            });
+            if result.hits.len() > max_bounces {
+                return result;
+            }
+            let normal_speed = velocity.dot(cast.normal2);
+            if normal_speed < 0.0 {
+                velocity -= cast.normal2 * (normal_speed * (1.0 + bounce));
+            }
+        }
+        result.truncated = result.time < max_time;
+        result
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    /// Rigid bodies with a solid collider within `radius` of `center`, one hit per body for its
+    /// closest collider, sorted from nearest to furthest. With `occlusion`, a body only counts
+    /// when a ray from the center reaches that closest point without first hitting a solid
+    /// collider of another body. Colliders without a parent body are never returned but still
+    /// occlude.
+    pub fn explosion_query(
+        &self,
+        world_handle: WorldHandle,
+        center: Vector,
+        radius: Real,
+        occlusion: bool,
+        handle_excluded_info: &QueryExcludedInfo,
+        physics_collision_objects: &PhysicsCollisionObjects,
+        physics_ids: &PhysicsIds,
+        space: &RapierSpace,
+    ) -> Vec<ExplosionHit> {
+        let Some(physics_world) = self.get_world(world_handle) else {
+            return Vec::new();
+        };
+        let predicate = |handle: ColliderHandle, _collider: &Collider| -> bool {
+            !space.is_handle_excluded_callback(
+                handle,
+                &physics_world.get_collider_user_data(handle),
+                handle_excluded_info,
+                physics_collision_objects,
+                physics_ids,
+            )
+        };
+        explosion_hits(physics_world, center, radius, occlusion, &predicate)
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn intersect_ray(
         &self,
@@ -390,91 +894,89 @@ impl PhysicsEngine {
         shape_info2: ShapeInfo,
     ) -> ShapeCastResult {
         let mut result = ShapeCastResult::new();
-        if let Some(raw_shared_shape1) = self.get_shape(shape_info1.handle) {
-            let shared_shape1 = scale_shape(raw_shared_shape1, shape_info1);
-            if let Some(raw_shared_shape2) = self.get_shape(shape_info2.handle) {
-                let shared_shape2 = scale_shape(raw_shared_shape2, shape_info2);
-                let shape_transform1 = shape_info1.transform;
-                let shape_transform2 = shape_info2.transform;
-                let shape_cast_options = ShapeCastOptions {
-                    max_time_of_impact: 1.0,
-                    stop_at_penetration: true,
-                    compute_impact_geometry_on_penetration: true,
-                    ..Default::default()
-                };
-                // Stationary shapes
-                if shape_vel1.length_squared() < DEFAULT_EPSILON
-                    && shape_vel2.length_squared() < DEFAULT_EPSILON
-                {
-                    let pos12 = shape_transform1.inv_mul(&shape_transform2);
-                    // Parry only reports a contact strictly closer than the prediction
-                    // distance, so querying with zero misses shapes that rest exactly
-                    // touching, which is where the solver leaves them. The depth test below
-                    // is what actually decides whether they collide.
-                    let contact_result = separation_ray_query_dispatcher()
-                        .contact(
-                            &pos12,
-                            shared_shape1.as_ref(),
-                            shared_shape2.as_ref(),
-                            contact_prediction(0.0),
-                        )
-                        .map(|contact| {
-                            contact.map(|mut contact| {
-                                contact.transform_by_mut(&shape_transform1, &shape_transform2);
-                                contact
-                            })
-                        });
-                    match contact_result {
-                        Ok(None) => {}
-                        Ok(Some(contact)) => {
-                            // the distance is negative if there is intersection
-                            if contact.dist <= 0.0 {
-                                result.toi = 0.0;
-                                result.collided = true;
-                                // parry::query::contact() returns results in world space
-                                result.normal1 = contact.normal1;
-                                result.normal2 = contact.normal2;
-                                result.pixel_witness1 = contact.point1;
-                                result.pixel_witness2 = contact.point2;
-                            }
-                        }
-                        Err(err) => {
-                            godot_error!("contact error: {:?}", err);
-                        }
-                    }
-                    return result;
-                }
+        if let Some(shared_shape1) = self.get_scaled_shape(shape_info1)
+            && let Some(shared_shape2) = self.get_scaled_shape(shape_info2)
+        {
+            let shape_transform1 = shape_info1.transform;
+            let shape_transform2 = shape_info2.transform;
+            let shape_cast_options = ShapeCastOptions {
+                max_time_of_impact: 1.0,
+                stop_at_penetration: true,
+                compute_impact_geometry_on_penetration: true,
+                ..Default::default()
+            };
+            // Stationary shapes
+            if shape_vel1.length_squared() < DEFAULT_EPSILON
+                && shape_vel2.length_squared() < DEFAULT_EPSILON
+            {
                 let pos12 = shape_transform1.inv_mul(&shape_transform2);
-                let vel12 = shape_transform1.rotation.inverse() * (shape_vel2 - shape_vel1);
-                let toi_result = separation_ray_query_dispatcher().cast_shapes(
-                    &pos12,
-                    vel12,
-                    shared_shape1.as_ref(),
-                    shared_shape2.as_ref(),
-                    shape_cast_options,
-                );
-                match toi_result {
+                // Parry only reports a contact strictly closer than the prediction
+                // distance, so querying with zero misses shapes that rest exactly
+                // touching, which is where the solver leaves them. The depth test below
+                // is what actually decides whether they collide.
+                let contact_result = separation_ray_query_dispatcher()
+                    .contact(
+                        &pos12,
+                        shared_shape1.as_ref(),
+                        shared_shape2.as_ref(),
+                        contact_prediction(0.0),
+                    )
+                    .map(|contact| {
+                        contact.map(|mut contact| {
+                            contact.transform_by_mut(&shape_transform1, &shape_transform2);
+                            contact
+                        })
+                    });
+                match contact_result {
                     Ok(None) => {}
-                    Ok(Some(hit)) => {
-                        if hit.status == ShapeCastStatus::Failed
-                            || hit.status == ShapeCastStatus::OutOfIterations
-                        {
-                            godot_warn!("shape collide status warn: {:?}", hit.status);
+                    Ok(Some(contact)) => {
+                        // the distance is negative if there is intersection
+                        if contact.dist <= 0.0 {
+                            result.toi = 0.0;
+                            result.collided = true;
+                            // parry::query::contact() returns results in world space
+                            result.normal1 = contact.normal1;
+                            result.normal2 = contact.normal2;
+                            result.pixel_witness1 = contact.point1;
+                            result.pixel_witness2 = contact.point2;
                         }
-                        result.collided = true;
-                        result.toi = hit.time_of_impact;
-                        // parry::query::cast_shapes() returns results in each shape's local space
-                        result.normal1 = shape_transform1.rotation * hit.normal1;
-                        result.normal2 = shape_transform2.rotation * hit.normal2;
-                        result.pixel_witness1 =
-                            shape_transform1 * hit.witness1 + shape_vel1 * hit.time_of_impact;
-                        result.pixel_witness2 =
-                            shape_transform2 * hit.witness2 + shape_vel2 * hit.time_of_impact;
                     }
                     Err(err) => {
-                        godot_error!("toi error: {:?}", err);
+                        godot_error!("contact error: {:?}", err);
                     }
                 }
+                return result;
+            }
+            let pos12 = shape_transform1.inv_mul(&shape_transform2);
+            let vel12 = shape_transform1.rotation.inverse() * (shape_vel2 - shape_vel1);
+            let toi_result = separation_ray_query_dispatcher().cast_shapes(
+                &pos12,
+                vel12,
+                shared_shape1.as_ref(),
+                shared_shape2.as_ref(),
+                shape_cast_options,
+            );
+            match toi_result {
+                Ok(None) => {}
+                Ok(Some(hit)) => {
+                    if hit.status == ShapeCastStatus::Failed
+                        || hit.status == ShapeCastStatus::OutOfIterations
+                    {
+                        godot_warn!("shape collide status warn: {:?}", hit.status);
+                    }
+                    result.collided = true;
+                    result.toi = hit.time_of_impact;
+                    // parry::query::cast_shapes() returns results in each shape's local space
+                    result.normal1 = shape_transform1.rotation * hit.normal1;
+                    result.normal2 = shape_transform2.rotation * hit.normal2;
+                    result.pixel_witness1 =
+                        shape_transform1 * hit.witness1 + shape_vel1 * hit.time_of_impact;
+                    result.pixel_witness2 =
+                        shape_transform2 * hit.witness2 + shape_vel2 * hit.time_of_impact;
+                }
+                Err(err) => {
+                    godot_error!("toi error: {:?}", err);
+                }
             }
         }
         result
@@ -500,13 +1002,12 @@ impl PhysicsEngine {
         if max_results == 0 {
             return result_count;
         }
-        let Some(raw_shared_shape) = self.get_shape(shape_info.handle) else {
+        let Some(shared_shape) = self.get_scaled_shape(shape_info) else {
             return result_count;
         };
         let Some(physics_world) = self.get_world(world_handle) else {
             return result_count;
         };
-        let shared_shape = scale_shape(raw_shared_shape, shape_info);
         let shape_transform = shape_info.transform;
         let mut filter = QueryFilter::new();
         if !collide_with_body {
@@ -641,37 +1142,113 @@ impl PhysicsEngine {
         needs_exact: bool,
     ) -> Vec<ShapeCastResult> {
         let mut results: Vec<ShapeCastResult> = Vec::new();
-        if let Some(raw_shared_shape) = self.get_shape(shape_info.handle) {
-            let shared_shape = scale_shape(raw_shared_shape, shape_info);
-            if let Some(physics_world) = self.get_world(world_handle) {
-                let shape_transform = shape_info.transform;
-                let mut filter = QueryFilter::new();
-                if !collide_with_body {
-                    filter = filter.exclude_solids();
-                }
-                if !collide_with_area {
-                    filter = filter.exclude_sensors();
-                }
-                let predicate = |handle: ColliderHandle, _collider: &Collider| -> bool {
-                    !space.is_handle_excluded_callback(
-                        handle,
-                        &physics_world.get_collider_user_data(handle),
-                        handle_excluded_info,
-                        physics_collision_objects,
-                        physics_ids,
+        if let Some(shared_shape) = self.get_scaled_shape(shape_info)
+            && let Some(physics_world) = self.get_world(world_handle)
+        {
+            let shape_transform = shape_info.transform;
+            let mut filter = QueryFilter::new();
+            if !collide_with_body {
+                filter = filter.exclude_solids();
+            }
+            if !collide_with_area {
+                filter = filter.exclude_sensors();
+            }
+            let predicate = |handle: ColliderHandle, _collider: &Collider| -> bool {
+                !space.is_handle_excluded_callback(
+                    handle,
+                    &physics_world.get_collider_user_data(handle),
+                    handle_excluded_info,
+                    physics_collision_objects,
+                    physics_ids,
+                )
+            };
+            filter.predicate = Some(&predicate);
+            let velocity_size = shape_vel.length();
+            if velocity_size < DEFAULT_EPSILON {
+                // Candidates come from an AABB loosened by the margin, not from a strict
+                // overlap test: a shape resting just outside the other still counts as a hit
+                // when it is within the margin, which is what Godot's queries report.
+                let prediction = contact_prediction(margin);
+                let query_aabb = shared_shape
+                    .compute_aabb(&shape_transform)
+                    .loosened(prediction);
+                for (collider_handle, collider) in physics_world
+                    .physics_objects
+                    .broad_phase
+                    .as_query_pipeline(
+                        physics_world
+                            .physics_objects
+                            .narrow_phase
+                            .query_dispatcher(),
+                        &physics_world.physics_objects.rigid_body_set,
+                        &physics_world.physics_objects.collider_set,
+                        filter,
                     )
+                    .intersect_aabb_conservative(query_aabb)
+                {
+                    let pos12 = shape_transform.inv_mul(collider.position());
+                    match physics_world
+                        .physics_objects
+                        .narrow_phase
+                        .query_dispatcher()
+                        .contact(&pos12, shared_shape.as_ref(), collider.shape(), prediction)
+                    {
+                        // Parry only reports a contact strictly closer than the prediction
+                        // distance, so querying with `margin` alone misses shapes that rest
+                        // exactly touching, which is where rapier's solver leaves them.
+                        Ok(Some(contact)) if contact.dist <= margin => {
+                            let mut result = ShapeCastResult::new();
+                            result.collided = true;
+                            result.collider = collider_handle;
+                            result.user_data =
+                                physics_world.get_collider_user_data(collider_handle);
+                            result.toi = 0.0;
+                            // parry returns contacts in each shape's local space.
+                            result.normal1 = shape_transform.rotation * contact.normal1;
+                            result.normal2 = collider.rotation() * contact.normal2;
+                            result.pixel_witness1 = shape_transform * contact.point1;
+                            result.pixel_witness2 = collider.position() * contact.point2;
+                            results.push(result);
+                            if results.len()
+                                >= crate::servers::rapier_project_settings::motion_settings()
+                                    .max_shape_cast_results
+                            {
+                                break;
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(err) => godot_error!("contact error: {:?}", err),
+                    }
+                }
+            } else {
+                let shape_cast_options = ShapeCastOptions {
+                    max_time_of_impact: 1.0,
+                    stop_at_penetration: true,
+                    compute_impact_geometry_on_penetration: true,
+                    target_distance: margin,
                 };
-                filter.predicate = Some(&predicate);
-                let velocity_size = shape_vel.length();
-                if velocity_size < DEFAULT_EPSILON {
-                    // Candidates come from an AABB loosened by the margin, not from a strict
-                    // overlap test: a shape resting just outside the other still counts as a hit
-                    // when it is within the margin, which is what Godot's queries report.
-                    let prediction = contact_prediction(margin);
-                    let query_aabb = shared_shape
-                        .compute_aabb(&shape_transform)
-                        .loosened(prediction);
-                    for (collider_handle, collider) in physics_world
+                let mut cast_excludes: std::collections::HashSet<ColliderHandle> =
+                    std::collections::HashSet::new();
+                loop {
+                    let predicate = |handle: ColliderHandle, _collider: &Collider| -> bool {
+                        !cast_excludes.contains(&handle)
+                            && !space.is_handle_excluded_callback(
+                                handle,
+                                &physics_world.get_collider_user_data(handle),
+                                handle_excluded_info,
+                                physics_collision_objects,
+                                physics_ids,
+                            )
+                    };
+                    let mut cast_filter = QueryFilter::new();
+                    if !collide_with_body {
+                        cast_filter = cast_filter.exclude_solids();
+                    }
+                    if !collide_with_area {
+                        cast_filter = cast_filter.exclude_sensors();
+                    }
+                    cast_filter.predicate = Some(&predicate);
+                    let Some((collider_handle, hit)) = physics_world
                         .physics_objects
                         .broad_phase
                         .as_query_pipeline(
@@ -681,157 +1258,79 @@ impl PhysicsEngine {
                                 .query_dispatcher(),
                             &physics_world.physics_objects.rigid_body_set,
                             &physics_world.physics_objects.collider_set,
-                            filter,
+                            cast_filter,
                         )
-                        .intersect_aabb_conservative(query_aabb)
+                        .cast_shape(
+                            &shape_transform,
+                            shape_vel,
+                            shared_shape.as_ref(),
+                            shape_cast_options,
+                        )
+                    else {
+                        break;
+                    };
+                    if hit.status == ShapeCastStatus::Failed
+                        || hit.status == ShapeCastStatus::OutOfIterations
                     {
-                        let pos12 = shape_transform.inv_mul(collider.position());
-                        match physics_world
-                            .physics_objects
-                            .narrow_phase
-                            .query_dispatcher()
-                            .contact(&pos12, shared_shape.as_ref(), collider.shape(), prediction)
-                        {
-                            // Parry only reports a contact strictly closer than the prediction
-                            // distance, so querying with `margin` alone misses shapes that rest
-                            // exactly touching, which is where rapier's solver leaves them.
-                            Ok(Some(contact)) if contact.dist <= margin => {
-                                let mut result = ShapeCastResult::new();
-                                result.collided = true;
-                                result.collider = collider_handle;
-                                result.user_data =
-                                    physics_world.get_collider_user_data(collider_handle);
-                                result.toi = 0.0;
-                                // parry returns contacts in each shape's local space.
-                                result.normal1 = shape_transform.rotation * contact.normal1;
-                                result.normal2 = collider.rotation() * contact.normal2;
-                                result.pixel_witness1 = shape_transform * contact.point1;
-                                result.pixel_witness2 = collider.position() * contact.point2;
-                                results.push(result);
-                                if results.len()
-                                    >= crate::servers::rapier_project_settings::motion_settings()
-                                        .max_shape_cast_results
-                                {
-                                    break;
-                                }
-                            }
-                            Ok(_) => {}
-                            Err(err) => godot_error!("contact error: {:?}", err),
-                        }
+                        godot_warn!("shape casting status warn: {:?}", hit.status);
                     }
-                } else {
-                    let shape_cast_options = ShapeCastOptions {
-                        max_time_of_impact: 1.0,
-                        stop_at_penetration: true,
-                        compute_impact_geometry_on_penetration: true,
-                        target_distance: margin,
-                    };
-                    let mut cast_excludes: std::collections::HashSet<ColliderHandle> =
-                        std::collections::HashSet::new();
-                    loop {
-                        let predicate = |handle: ColliderHandle, _collider: &Collider| -> bool {
-                            !cast_excludes.contains(&handle)
-                                && !space.is_handle_excluded_callback(
-                                    handle,
-                                    &physics_world.get_collider_user_data(handle),
-                                    handle_excluded_info,
-                                    physics_collision_objects,
-                                    physics_ids,
-                                )
-                        };
-                        let mut cast_filter = QueryFilter::new();
-                        if !collide_with_body {
-                            cast_filter = cast_filter.exclude_solids();
-                        }
-                        if !collide_with_area {
-                            cast_filter = cast_filter.exclude_sensors();
-                        }
-                        cast_filter.predicate = Some(&predicate);
-                        let Some((collider_handle, hit)) = physics_world
-                            .physics_objects
-                            .broad_phase
-                            .as_query_pipeline(
-                                physics_world
-                                    .physics_objects
-                                    .narrow_phase
-                                    .query_dispatcher(),
-                                &physics_world.physics_objects.rigid_body_set,
-                                &physics_world.physics_objects.collider_set,
-                                cast_filter,
-                            )
-                            .cast_shape(
-                                &shape_transform,
-                                shape_vel,
-                                shared_shape.as_ref(),
-                                shape_cast_options,
-                            )
-                        else {
-                            break;
-                        };
-                        if hit.status == ShapeCastStatus::Failed
-                            || hit.status == ShapeCastStatus::OutOfIterations
-                        {
-                            godot_warn!("shape casting status warn: {:?}", hit.status);
-                        }
-                        if needs_exact && hit.time_of_impact == 0.0 {
-                            cast_excludes.insert(collider_handle);
-                            continue;
-                        }
-                        if let Some(collider) = physics_world
-                            .physics_objects
-                            .collider_set
-                            .get(collider_handle)
-                        {
-                            let mut result = ShapeCastResult::new();
-                            result.collided = true;
-                            result.collider = collider_handle;
-                            result.user_data =
-                                physics_world.get_collider_user_data(collider_handle);
-                            result.toi = hit.time_of_impact;
-                            result.toi_unsafe = hit.time_of_impact;
-
-                            // In QueryPipeline::cast_shapes(),
-                            // world shapes is the hidden first parameter, and the scanner shape is the second,
-                            // so the order of normals and witnesses needs to be swapped
-                            // result.pixel_witness1 <- hit.witness2 transformed from scanner shape's local space
-                            // result.pixel_witness2 <- hit.witness1 (world shape's local space, no need to transform)
-                            result.normal1 = shape_transform.rotation * hit.normal2;
-                            result.normal2 = hit.normal1;
-                            result.pixel_witness1 =
-                                shape_transform * hit.witness2 + shape_vel * hit.time_of_impact;
-                            result.pixel_witness2 = hit.witness1;
-                            // `toi` is the furthest the shape can advance without colliding and
-                            // `toi_unsafe` the first fraction where it does, so the two must
-                            // not be equal. The cast stops on contact, leaving no separation
-                            // to close, so advancing by one contact prediction is enough to
-                            // overlap at any approach angle.
-                            if needs_exact {
-                                let mut hit_transform = shape_transform;
-                                hit_transform.translation += shape_vel * hit.time_of_impact;
-                                let pos12 = hit_transform.inv_mul(collider.position());
-                                let separation = physics_world
-                                    .physics_objects
-                                    .narrow_phase
-                                    .query_dispatcher()
-                                    .distance(&pos12, shared_shape.as_ref(), collider.shape())
-                                    .unwrap_or(0.0)
-                                    .max(0.0);
-                                result.toi_unsafe = (result.toi
-                                    + (separation + MIN_CONTACT_PREDICTION) / velocity_size)
-                                    .min(1.0);
-                            }
-                            results.push(result);
-                        } else {
-                            godot_error!("collider not found");
-                        }
+                    if needs_exact && hit.time_of_impact == 0.0 {
                         cast_excludes.insert(collider_handle);
-                        if needs_exact
-                            || results.len()
-                                >= crate::servers::rapier_project_settings::motion_settings()
-                                    .max_shape_cast_results
-                        {
-                            break;
+                        continue;
+                    }
+                    if let Some(collider) = physics_world
+                        .physics_objects
+                        .collider_set
+                        .get(collider_handle)
+                    {
+                        let mut result = ShapeCastResult::new();
+                        result.collided = true;
+                        result.collider = collider_handle;
+                        result.user_data = physics_world.get_collider_user_data(collider_handle);
+                        result.toi = hit.time_of_impact;
+                        result.toi_unsafe = hit.time_of_impact;
+
+                        // In QueryPipeline::cast_shapes(),
+                        // world shapes is the hidden first parameter, and the scanner shape is the second,
+                        // so the order of normals and witnesses needs to be swapped
+                        // result.pixel_witness1 <- hit.witness2 transformed from scanner shape's local space
+                        // result.pixel_witness2 <- hit.witness1 (world shape's local space, no need to transform)
+                        result.normal1 = shape_transform.rotation * hit.normal2;
+                        result.normal2 = hit.normal1;
+                        result.pixel_witness1 =
+                            shape_transform * hit.witness2 + shape_vel * hit.time_of_impact;
+                        result.pixel_witness2 = hit.witness1;
+                        // `toi` is the furthest the shape can advance without colliding and
+                        // `toi_unsafe` the first fraction where it does, so the two must
+                        // not be equal. The cast stops on contact, leaving no separation
+                        // to close, so advancing by one contact prediction is enough to
+                        // overlap at any approach angle.
+                        if needs_exact {
+                            let mut hit_transform = shape_transform;
+                            hit_transform.translation += shape_vel * hit.time_of_impact;
+                            let pos12 = hit_transform.inv_mul(collider.position());
+                            let separation = physics_world
+                                .physics_objects
+                                .narrow_phase
+                                .query_dispatcher()
+                                .distance(&pos12, shared_shape.as_ref(), collider.shape())
+                                .unwrap_or(0.0)
+                                .max(0.0);
+                            result.toi_unsafe = (result.toi
+                                + (separation + MIN_CONTACT_PREDICTION) / velocity_size)
+                                .min(1.0);
                         }
+                        results.push(result);
+                    } else {
+                        godot_error!("collider not found");
+                    }
+                    cast_excludes.insert(collider_handle);
+                    if needs_exact
+                        || results.len()
+                            >= crate::servers::rapier_project_settings::motion_settings()
+                                .max_shape_cast_results
+                    {
+                        break;
                     }
                 }
             }
@@ -915,44 +1414,357 @@ impl PhysicsEngine {
     ) -> ContactResult {
         let mut result = ContactResult::default();
         let prediction = contact_prediction(margin);
-        if let Some(raw_shared_shape1) = self.get_shape(shape_info1.handle) {
-            let shared_shape1 = scale_shape(raw_shared_shape1, shape_info1);
-            if let Some(raw_shared_shape2) = self.get_shape(shape_info2.handle) {
-                let shared_shape2 = scale_shape(raw_shared_shape2, shape_info2);
-                let shape_transform1 = shape_info1.transform;
-                let shape_transform2 = shape_info2.transform;
-                let pos12 = shape_transform1.inv_mul(&shape_transform2);
-                match separation_ray_query_dispatcher()
-                    .contact(
-                        &pos12,
-                        shared_shape1.as_ref(),
-                        shared_shape2.as_ref(),
-                        prediction,
-                    )
-                    .map(|contact| {
-                        contact.map(|mut contact| {
-                            contact.transform_by_mut(&shape_transform1, &shape_transform2);
-                            contact
-                        })
-                    }) {
-                    Ok(None) => {}
-                    Ok(Some(contact)) => {
-                        // the distance is negative if there is intersection
-                        // and positive if the objects are separated by distance less than margin
-                        result.pixel_distance = contact.dist;
-                        result.within_margin = contact.dist > 0.0;
-                        result.collided = true;
-                        result.normal1 = contact.normal1;
-                        result.normal2 = contact.normal2;
-                        result.pixel_point1 = contact.point1 + contact.normal1.mul(prediction);
-                        result.pixel_point2 = contact.point2;
-                    }
-                    Err(err) => {
-                        godot_error!("Shape Contact Error: {:?}", err);
-                    }
+        if let Some(shared_shape1) = self.get_scaled_shape(shape_info1)
+            && let Some(shared_shape2) = self.get_scaled_shape(shape_info2)
+        {
+            let shape_transform1 = shape_info1.transform;
+            let shape_transform2 = shape_info2.transform;
+            let pos12 = shape_transform1.inv_mul(&shape_transform2);
+            match separation_ray_query_dispatcher()
+                .contact(
+                    &pos12,
+                    shared_shape1.as_ref(),
+                    shared_shape2.as_ref(),
+                    prediction,
+                )
+                .map(|contact| {
+                    contact.map(|mut contact| {
+                        contact.transform_by_mut(&shape_transform1, &shape_transform2);
+                        contact
+                    })
+                }) {
+                Ok(None) => {}
+                Ok(Some(contact)) => {
+                    // the distance is negative if there is intersection
+                    // and positive if the objects are separated by distance less than margin
+                    result.pixel_distance = contact.dist;
+                    result.within_margin = contact.dist > 0.0;
+                    result.collided = true;
+                    result.normal1 = contact.normal1;
+                    result.normal2 = contact.normal2;
+                    result.pixel_point1 = contact.point1 + contact.normal1.mul(prediction);
+                    result.pixel_point2 = contact.point2;
+                }
+                Err(err) => {
+                    godot_error!("Shape Contact Error: {:?}", err);
                 }
             }
         }
         result
     }
+
+    /// Signed distance between two shapes and their closest points, however far apart they
+    /// are. The distance is negative when the shapes overlap, and `collided` is only false when
+    /// either shape is missing.
+    pub fn shapes_distance(&self, shape_info1: ShapeInfo, shape_info2: ShapeInfo) -> ContactResult {
+        if let Some(shared_shape1) = self.get_scaled_shape(shape_info1)
+            && let Some(shared_shape2) = self.get_scaled_shape(shape_info2)
+            && let Some(contact) = closest_contact(
+                &shape_info1.transform,
+                shared_shape1.as_ref(),
+                &shape_info2.transform,
+                shared_shape2.as_ref(),
+            )
+        {
+            return distance_result(&contact);
+        }
+        ContactResult::default()
+    }
+
+    /// Smallest signed distance between any enabled, non-sensor collider of the first body and
+    /// any of the second, as [`PhysicsEngine::shapes_distance`] reports it.
+    pub fn bodies_distance(
+        &self,
+        world_handle: WorldHandle,
+        body_handle_1: RigidBodyHandle,
+        body_handle_2: RigidBodyHandle,
+    ) -> ContactResult {
+        let Some(physics_world) = self.get_world(world_handle) else {
+            return ContactResult::default();
+        };
+        let rigid_body_set = &physics_world.physics_objects.rigid_body_set;
+        let collider_set = &physics_world.physics_objects.collider_set;
+        let (Some(body1), Some(body2)) = (
+            rigid_body_set.get(body_handle_1),
+            rigid_body_set.get(body_handle_2),
+        ) else {
+            return ContactResult::default();
+        };
+        let is_measurable = |collider: &Collider| !collider.is_sensor() && collider.is_enabled();
+        let mut closest: Option<rapier::parry::query::Contact> = None;
+        for &collider_handle_1 in body1.colliders() {
+            let Some(collider1) = collider_set.get(collider_handle_1) else {
+                continue;
+            };
+            if !is_measurable(collider1) {
+                continue;
+            }
+            for &collider_handle_2 in body2.colliders() {
+                let Some(collider2) = collider_set.get(collider_handle_2) else {
+                    continue;
+                };
+                if !is_measurable(collider2) {
+                    continue;
+                }
+                if let Some(contact) = closest_contact(
+                    collider1.position(),
+                    collider1.shape(),
+                    collider2.position(),
+                    collider2.shape(),
+                ) && closest.is_none_or(|closest| contact.dist < closest.dist)
+                {
+                    closest = Some(contact);
+                }
+            }
+        }
+        closest
+            .map(|contact| distance_result(&contact))
+            .unwrap_or_default()
+    }
+}
+#[cfg(all(test, feature = "dim3"))]
+mod tests {
+    use super::*;
+    fn create_world(physics_engine: &mut PhysicsEngine, world_handle: WorldHandle) {
+        let settings = WorldSettings {
+            particle_radius: 1.0,
+            smoothing_factor: 1.0,
+            counters_enabled: false,
+            boundary_coef: 1.0,
+            #[cfg(feature = "parallel")]
+            thread_count: 1,
+        };
+        physics_engine.world_create(&settings, world_handle);
+    }
+    /// Steps the bare rapier pipeline without gravity, which also fills the broad phase the
+    /// queries read from.
+    fn step_world(physics_engine: &mut PhysicsEngine, world_handle: WorldHandle, dt: Real) {
+        let physics_world = physics_engine.get_mut_world(world_handle).unwrap();
+        let physics_objects = &mut physics_world.physics_objects;
+        physics_world.physics_pipeline.step(
+            Vector::ZERO,
+            &IntegrationParameters {
+                dt,
+                ..Default::default()
+            },
+            &mut physics_objects.island_manager,
+            &mut physics_objects.broad_phase,
+            &mut physics_objects.narrow_phase,
+            &mut physics_objects.rigid_body_set,
+            &mut physics_objects.collider_set,
+            &mut physics_objects.impulse_joint_set,
+            &mut physics_objects.multibody_joint_set,
+            &mut physics_objects.ccd_solver,
+            &(),
+            &(),
+        );
+    }
+    fn create_ball_body(
+        physics_engine: &mut PhysicsEngine,
+        world_handle: WorldHandle,
+        shape_handle: ShapeHandle,
+        body_type: BodyType,
+        pos: Vector,
+    ) -> (RigidBodyHandle, ColliderHandle) {
+        let body_handle = physics_engine.body_create(
+            world_handle,
+            pos,
+            Rotation::IDENTITY,
+            body_type,
+            0.0,
+            0.0,
+            0.0,
+        );
+        let collider_handle = physics_engine.collider_create_solid(
+            world_handle,
+            shape_handle,
+            &Material::new(1, 1, 0),
+            body_handle,
+            &UserData::invalid_user_data(),
+        );
+        (body_handle, collider_handle)
+    }
+    #[test]
+    fn penetrations_report_overlap_depth_and_margin_distance() {
+        let mut physics_engine = PhysicsEngine::default();
+        let world_handle = 1;
+        let shape_handle = 2;
+        create_world(&mut physics_engine, world_handle);
+        physics_engine.shape_create_circle(1.0, shape_handle);
+        let (body, _) = create_ball_body(
+            &mut physics_engine,
+            world_handle,
+            shape_handle,
+            BodyType::Static,
+            Vector::ZERO,
+        );
+        let (_, overlapping) = create_ball_body(
+            &mut physics_engine,
+            world_handle,
+            shape_handle,
+            BodyType::Static,
+            Vector::new(1.5, 0.0, 0.0),
+        );
+        let (_, near) = create_ball_body(
+            &mut physics_engine,
+            world_handle,
+            shape_handle,
+            BodyType::Static,
+            Vector::new(-2.05, 0.0, 0.0),
+        );
+        step_world(&mut physics_engine, world_handle, 1.0 / 60.0);
+        let penetrations = physics_engine.body_get_penetrations(world_handle, body, 0.0, 8);
+        assert_eq!(penetrations.len(), 1);
+        let penetration = penetrations[0];
+        assert_eq!(penetration.collider, overlapping);
+        assert!((penetration.depth - 0.5).abs() < 1.0e-4);
+        assert!((penetration.normal - Vector::new(-1.0, 0.0, 0.0)).length() < 1.0e-4);
+        assert!((penetration.pixel_point - Vector::new(1.0, 0.0, 0.0)).length() < 1.0e-4);
+        assert!((penetration.pixel_collider_point - Vector::new(0.5, 0.0, 0.0)).length() < 1.0e-4);
+        // Within the margin the shapes only come close, which shows as a negative depth.
+        let mut penetrations = physics_engine.body_get_penetrations(world_handle, body, 0.1, 8);
+        penetrations.sort_by(|a, b| b.depth.total_cmp(&a.depth));
+        assert_eq!(penetrations.len(), 2);
+        assert_eq!(penetrations[1].collider, near);
+        assert!((penetrations[1].depth + 0.05).abs() < 1.0e-4);
+        assert_eq!(
+            physics_engine
+                .body_get_penetrations(world_handle, body, 0.1, 1)
+                .len(),
+            1
+        );
+    }
+    #[test]
+    fn time_of_impact_of_balls_moving_towards_each_other() {
+        let mut physics_engine = PhysicsEngine::default();
+        let world_handle = 1;
+        let shape_handle = 2;
+        create_world(&mut physics_engine, world_handle);
+        physics_engine.shape_create_circle(1.0, shape_handle);
+        let (body1, _) = create_ball_body(
+            &mut physics_engine,
+            world_handle,
+            shape_handle,
+            BodyType::Dynamic,
+            Vector::ZERO,
+        );
+        let (body2, collider2) = create_ball_body(
+            &mut physics_engine,
+            world_handle,
+            shape_handle,
+            BodyType::Dynamic,
+            Vector::new(5.0, 0.0, 0.0),
+        );
+        physics_engine.body_set_linear_velocity(world_handle, body1, Vector::new(1.0, 0.0, 0.0));
+        physics_engine.body_set_linear_velocity(world_handle, body2, Vector::new(-1.0, 0.0, 0.0));
+        // A gap of 3 closed at 2 per second.
+        let result = physics_engine.bodies_time_of_impact(world_handle, body1, body2, 10.0);
+        assert!(result.collided);
+        assert!((result.toi - 1.5).abs() < 1.0e-3);
+        assert_eq!(result.collider, collider2);
+        assert!((result.pixel_witness1 - Vector::new(2.5, 0.0, 0.0)).length() < 1.0e-3);
+        assert!((result.pixel_witness2 - Vector::new(2.5, 0.0, 0.0)).length() < 1.0e-3);
+        assert!((result.normal1 - Vector::new(1.0, 0.0, 0.0)).length() < 1.0e-3);
+        assert!((result.normal2 - Vector::new(-1.0, 0.0, 0.0)).length() < 1.0e-3);
+        let result = physics_engine.bodies_time_of_impact(world_handle, body1, body2, 1.0);
+        assert!(!result.collided);
+        assert_eq!(result.toi, 1.0);
+    }
+    #[test]
+    fn shapes_distance_of_separated_and_overlapping_shapes() {
+        use godot::prelude::Transform3D;
+        use godot::prelude::Vector3;
+        let mut physics_engine = PhysicsEngine::default();
+        let ball_handle = 1;
+        let floor_handle = 2;
+        physics_engine.shape_create_circle(1.0, ball_handle);
+        // A 20x20 grid of triangles lying at y = 0.
+        let size = 20;
+        let mut points = Vec::new();
+        for z in 0..=size {
+            for x in 0..=size {
+                points.push(Vector::new(x as Real - 10.0, 0.0, z as Real - 10.0));
+            }
+        }
+        let mut indices = Vec::new();
+        for z in 0..size {
+            for x in 0..size {
+                let corner = z * (size + 1) + x;
+                indices.push([corner, corner + size + 1, corner + 1]);
+                indices.push([corner + 1, corner + size + 1, corner + size + 2]);
+            }
+        }
+        physics_engine.shape_create_concave_polyline(&points, Some(indices), true, floor_handle);
+        let at = |handle, x, y| {
+            shape_info_from_body_shape(
+                handle,
+                Transform3D::IDENTITY.translated(Vector3::new(x, y, 0.0)),
+            )
+        };
+        let result =
+            physics_engine.shapes_distance(at(ball_handle, 0.0, 0.0), at(ball_handle, 5.0, 0.0));
+        assert!(result.collided);
+        assert!((result.pixel_distance - 3.0).abs() < 1.0e-4);
+        assert!((result.pixel_point1 - Vector::new(1.0, 0.0, 0.0)).length() < 1.0e-4);
+        assert!((result.pixel_point2 - Vector::new(4.0, 0.0, 0.0)).length() < 1.0e-4);
+        assert!((result.normal1 - Vector::new(1.0, 0.0, 0.0)).length() < 1.0e-4);
+        let result =
+            physics_engine.shapes_distance(at(ball_handle, 0.0, 0.0), at(ball_handle, 1.5, 0.0));
+        assert!((result.pixel_distance + 0.5).abs() < 1.0e-4);
+        let result =
+            physics_engine.shapes_distance(at(ball_handle, 0.5, 5.0), at(floor_handle, 0.0, 0.0));
+        assert!((result.pixel_distance - 4.0).abs() < 1.0e-4);
+        assert!((result.pixel_point2 - Vector::new(0.5, 0.0, 0.0)).length() < 1.0e-4);
+        assert!((result.normal1 - Vector::new(0.0, -1.0, 0.0)).length() < 1.0e-4);
+        assert!(
+            !physics_engine
+                .shapes_distance(at(ball_handle, 0.0, 0.0), at(3, 0.0, 0.0))
+                .collided
+        );
+    }
+    #[test]
+    fn explosion_hits_are_sorted_and_occluded() {
+        let mut physics_engine = PhysicsEngine::default();
+        let world_handle = 1;
+        let shape_handle = 2;
+        create_world(&mut physics_engine, world_handle);
+        physics_engine.shape_create_circle(1.0, shape_handle);
+        let mut create_ball = |pos| {
+            create_ball_body(
+                &mut physics_engine,
+                world_handle,
+                shape_handle,
+                BodyType::Static,
+                pos,
+            )
+            .1
+        };
+        let near = create_ball(Vector::new(3.0, 0.0, 0.0));
+        let behind = create_ball(Vector::new(6.0, 0.0, 0.0));
+        let above = create_ball(Vector::new(0.0, 4.0, 0.0));
+        create_ball(Vector::new(20.0, 0.0, 0.0));
+        step_world(&mut physics_engine, world_handle, 1.0 / 60.0);
+        let physics_world = physics_engine.get_world(world_handle).unwrap();
+        let hits = explosion_hits(physics_world, Vector::ZERO, 8.0, false, &|_, _| true);
+        let colliders: Vec<ColliderHandle> = hits.iter().map(|hit| hit.collider).collect();
+        assert_eq!(colliders, [near, above, behind]);
+        assert!((hits[0].pixel_distance - 2.0).abs() < 1.0e-4);
+        assert!((hits[0].pixel_point - Vector::new(2.0, 0.0, 0.0)).length() < 1.0e-4);
+        assert!((hits[2].pixel_distance - 5.0).abs() < 1.0e-4);
+        // The near ball shields the one right behind it.
+        let hits = explosion_hits(physics_world, Vector::ZERO, 8.0, true, &|_, _| true);
+        let colliders: Vec<ColliderHandle> = hits.iter().map(|hit| hit.collider).collect();
+        assert_eq!(colliders, [near, above]);
+        // A center inside a collider hits it at distance zero.
+        let hits = explosion_hits(
+            physics_world,
+            Vector::new(3.0, 0.5, 0.0),
+            1.0,
+            true,
+            &|handle, _| handle != above,
+        );
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].collider, near);
+        assert_eq!(hits[0].pixel_distance, 0.0);
+    }
 }
@@ -1,3 +1,10 @@
+use std::hash::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::sync::Arc;
+
+use godot::builtin::math::FloatExt;
+use rapier::math::DIM;
 use rapier::prelude::*;
 use types::Transform;
 
@@ -132,14 +139,185 @@ fn create_convex_polyline_unmodified(points: Vec<Vector>) -> Option<SharedShape>
     }
     None
 }
+/// A parry primitive Godot has no shape resource for, built by custom shapes.
+#[cfg(feature = "dim3")]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum PrimitiveShape {
+    Cone {
+        half_height: Real,
+        radius: Real,
+    },
+    Triangle(Vector, Vector, Vector),
+    Segment(Vector, Vector),
+    /// A capsule from `a` to `b` whose radius goes from `radius_a` to `radius_b`.
+    Capsule {
+        a: Vector,
+        b: Vector,
+        radius_a: Real,
+        radius_b: Real,
+    },
+    /// Primitives placed in one shape. They cannot be compounds themselves.
+    Compound(Vec<(Pose, PrimitiveShape)>),
+}
+#[cfg(feature = "dim3")]
+fn primitive_shape(primitive: &PrimitiveShape) -> Option<SharedShape> {
+    match primitive {
+        PrimitiveShape::Cone {
+            half_height,
+            radius,
+        } => Some(SharedShape::cone(*half_height, *radius)),
+        PrimitiveShape::Triangle(a, b, c) => Some(SharedShape::triangle(*a, *b, *c)),
+        PrimitiveShape::Segment(a, b) => Some(SharedShape::segment(*a, *b)),
+        PrimitiveShape::Capsule {
+            a,
+            b,
+            radius_a,
+            radius_b,
+        } => {
+            if radius_a == radius_b {
+                return Some(SharedShape::capsule(*a, *b, *radius_a));
+            }
+            // Parry capsules have a single radius, so a tapered one is the convex hull of the
+            // spheres at its ends.
+            let subdivisions =
+                crate::servers::rapier_project_settings::motion_settings().shape_scale_subdivisions;
+            let mut points = Vec::new();
+            for (center, radius) in [(a, radius_a), (b, radius_b)] {
+                let (vertices, _) = Ball::new(*radius).to_trimesh(subdivisions, subdivisions);
+                points.extend(vertices.into_iter().map(|vertex| vertex + *center));
+            }
+            SharedShape::convex_hull(&points)
+        }
+        PrimitiveShape::Compound(parts) => {
+            if parts.is_empty() {
+                return None;
+            }
+            let mut shapes = Vec::with_capacity(parts.len());
+            for (position, part) in parts {
+                if matches!(part, PrimitiveShape::Compound(_)) {
+                    return None;
+                }
+                shapes.push((*position, primitive_shape(part)?));
+            }
+            Some(SharedShape::compound(shapes))
+        }
+    }
+}
 #[derive(Copy, Clone, Debug)]
 pub struct ShapeInfo {
     pub handle: ShapeHandle,
     pub transform: Pose,
     #[cfg(feature = "dim2")]
     pub skew: Real,
+    /// Shear applied to the shape before its scale, the identity for most transforms.
+    #[cfg(feature = "dim3")]
+    pub skew: Mat3,
     pub scale: Vector,
 }
+#[cfg(feature = "single")]
+type RealBits = u32;
+#[cfg(feature = "double")]
+type RealBits = u64;
+/// The exact scale and skew a query scaled a shape with.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ScaleKey {
+    scale: [RealBits; DIM],
+    #[cfg(feature = "dim2")]
+    skew: RealBits,
+    #[cfg(feature = "dim3")]
+    skew: [RealBits; 9],
+}
+impl ScaleKey {
+    pub fn new(shape_info: &ShapeInfo) -> Self {
+        Self {
+            scale: shape_info.scale.to_array().map(Real::to_bits),
+            #[cfg(feature = "dim2")]
+            skew: shape_info.skew.to_bits(),
+            #[cfg(feature = "dim3")]
+            skew: shape_info.skew.to_cols_array().map(Real::to_bits),
+        }
+    }
+}
+/// How many scales of one shape are kept, the oldest being dropped first.
+const MAX_SCALED_SHAPES: usize = 8;
+fn hash_points(points: &[Vector], hasher: &mut DefaultHasher) {
+    for point in points {
+        point.to_array().map(Real::to_bits).hash(hasher);
+    }
+}
+/// Hash of the content of the shapes instanced scenes create many identical copies of: convex
+/// shapes and concave meshes. `None` for the others, which are not shared.
+fn shape_content_hash(shape: &SharedShape) -> Option<u64> {
+    let mut hasher = DefaultHasher::new();
+    (shape.shape_type() as u32).hash(&mut hasher);
+    match shape.shape_type() {
+        #[cfg(feature = "dim2")]
+        ShapeType::ConvexPolygon => hash_points(shape.as_convex_polygon()?.points(), &mut hasher),
+        #[cfg(feature = "dim2")]
+        ShapeType::Polyline => {
+            let polyline = shape.as_polyline()?;
+            hash_points(polyline.vertices(), &mut hasher);
+            polyline.indices().hash(&mut hasher);
+        }
+        #[cfg(feature = "dim3")]
+        ShapeType::ConvexPolyhedron => {
+            hash_points(shape.as_convex_polyhedron()?.points(), &mut hasher)
+        }
+        #[cfg(feature = "dim3")]
+        ShapeType::TriMesh => {
+            let trimesh = shape.as_trimesh()?;
+            hash_points(trimesh.vertices(), &mut hasher);
+            trimesh.indices().hash(&mut hasher);
+            trimesh.flags().bits().hash(&mut hasher);
+        }
+        _ => return None,
+    }
+    Some(hasher.finish())
+}
+/// Whether two shapes of the same content hash have the same content.
+fn same_shape_content(shape1: &SharedShape, shape2: &SharedShape) -> bool {
+    if shape1.shape_type() != shape2.shape_type() {
+        return false;
+    }
+    match shape1.shape_type() {
+        #[cfg(feature = "dim2")]
+        ShapeType::ConvexPolygon => shape1
+            .as_convex_polygon()
+            .zip(shape2.as_convex_polygon())
+            .is_some_and(|(convex1, convex2)| convex1.points() == convex2.points()),
+        #[cfg(feature = "dim2")]
+        ShapeType::Polyline => {
+            shape1
+                .as_polyline()
+                .zip(shape2.as_polyline())
+                .is_some_and(|(polyline1, polyline2)| {
+                    polyline1.vertices() == polyline2.vertices()
+                        && polyline1.indices() == polyline2.indices()
+                })
+        }
+        #[cfg(feature = "dim3")]
+        ShapeType::ConvexPolyhedron => shape1
+            .as_convex_polyhedron()
+            .zip(shape2.as_convex_polyhedron())
+            .is_some_and(|(convex1, convex2)| convex1.points() == convex2.points()),
+        #[cfg(feature = "dim3")]
+        ShapeType::TriMesh => {
+            shape1
+                .as_trimesh()
+                .zip(shape2.as_trimesh())
+                .is_some_and(|(trimesh1, trimesh2)| {
+                    trimesh1.flags() == trimesh2.flags()
+                        && trimesh1.vertices() == trimesh2.vertices()
+                        && trimesh1.indices() == trimesh2.indices()
+                })
+        }
+        _ => false,
+    }
+}
 #[cfg(feature = "dim2")]
 pub fn shape_info_from_body_shape(shape_handle: ShapeHandle, transform: Transform) -> ShapeInfo {
     ShapeInfo {
@@ -154,11 +332,19 @@ pub fn shape_info_from_body_shape(shape_handle: ShapeHandle, transform: Transfor
 }
 #[cfg(feature = "dim3")]
 pub fn shape_info_from_body_shape(shape_handle: ShapeHandle, transform: Transform) -> ShapeInfo {
-    let rotation = types::basis_to_rapier(transform.basis);
+    let (rotation, scale, skew) =
+        types::basis_shear_to_rapier(transform.basis).unwrap_or_else(|| {
+            (
+                types::basis_to_rapier(transform.basis),
+                types::transform_scale(&transform),
+                Mat3::IDENTITY,
+            )
+        });
     ShapeInfo {
         handle: shape_handle,
         transform: Pose::from_parts(vector_to_rapier(transform.origin), rotation),
-        scale: vector_to_rapier(types::transform_scale(&transform)),
+        skew,
+        scale: vector_to_rapier(scale),
     }
 }
 impl PhysicsEngine {
@@ -176,11 +362,11 @@ impl PhysicsEngine {
             None => polyline_points = sort_points_counter_clockwise(&points_vec),
         }
         if let Some(shape_data) = create_convex_polyline_unmodified(polyline_points) {
-            self.insert_shape(shape_data, handle);
+            self.insert_rounded_shape(shape_data, handle);
             return true;
         }
         if let Some(shape_data) = SharedShape::convex_hull(&points_vec) {
-            self.insert_shape(shape_data, handle);
+            self.insert_rounded_shape(shape_data, handle);
             return true;
         }
         false
@@ -188,24 +374,28 @@ impl PhysicsEngine {
 
     #[cfg(feature = "dim2")]
     pub fn shape_get_convex_polyline_points(&self, handle: ShapeHandle) -> Vec<Vector> {
-        if let Some(shape) = self.get_shape(handle)
-            && let Some(shape) = shape.as_convex_polygon()
-        {
-            let points = shape.points();
-            let points_vec = vec_to_point_array(points);
-            return points_vec;
+        if let Some(shape) = self.get_shape(handle) {
+            if let Some(shape) = shape.as_convex_polygon() {
+                return vec_to_point_array(shape.points());
+            }
+            if let Some(round) = shape.as_round_convex_polygon() {
+                let points = unshrink_convex_points(&round.inner_shape, round.border_radius);
+                return vec_to_point_array(&points);
+            }
         }
         vec![]
     }
 
     #[cfg(feature = "dim3")]
     pub fn shape_get_convex_polyline_points(&self, handle: ShapeHandle) -> Vec<Vector> {
-        if let Some(shape) = self.get_shape(handle)
-            && let Some(shape) = shape.as_convex_polyhedron()
-        {
-            let points = shape.points();
-            let points_vec = vec_to_point_array(points);
-            return points_vec;
+        if let Some(shape) = self.get_shape(handle) {
+            if let Some(shape) = shape.as_convex_polyhedron() {
+                return vec_to_point_array(shape.points());
+            }
+            if let Some(round) = shape.as_round_convex_polyhedron() {
+                let points = unshrink_convex_points(&round.inner_shape, round.border_radius);
+                return vec_to_point_array(&points);
+            }
         }
         vec![]
     }
@@ -218,7 +408,7 @@ impl PhysicsEngine {
     ) -> bool {
         let points_vec = point_array_to_vec(points);
         if let Some(shape_data) = SharedShape::convex_hull(&points_vec) {
-            self.insert_shape(shape_data, handle);
+            self.insert_rounded_shape(shape_data, handle);
             return true;
         }
         false
@@ -227,20 +417,23 @@ impl PhysicsEngine {
     #[cfg(feature = "dim2")]
     pub fn shape_create_box(&mut self, size: Vector, handle: ShapeHandle) {
         let shape = SharedShape::cuboid(0.5 * size.x, 0.5 * size.y);
-        self.insert_shape(shape, handle);
+        self.insert_rounded_shape(shape, handle);
     }
 
     #[cfg(feature = "dim3")]
     pub fn shape_create_box(&mut self, size: Vector, handle: ShapeHandle) {
         let shape = SharedShape::cuboid(0.5 * size.x, 0.5 * size.y, 0.5 * size.z);
-        self.insert_shape(shape, handle);
+        self.insert_rounded_shape(shape, handle);
     }
 
     pub fn shape_get_box_size(&self, shape_handle: ShapeHandle) -> Vector {
-        if let Some(shape) = self.get_shape(shape_handle)
-            && let Some(shape) = shape.as_cuboid()
-        {
-            return shape.half_extents;
+        if let Some(shape) = self.get_shape(shape_handle) {
+            if let Some(shape) = shape.as_cuboid() {
+                return shape.half_extents;
+            }
+            if let Some(round) = shape.as_round_cuboid() {
+                return round.inner_shape.half_extents + Vector::splat(round.border_radius);
+            }
         }
         Vector::ZERO
     }
@@ -295,31 +488,43 @@ impl PhysicsEngine {
     #[cfg(feature = "dim3")]
     pub fn shape_create_cylinder(&mut self, half_height: Real, radius: Real, handle: ShapeHandle) {
         let shape = SharedShape::cylinder(half_height, radius);
-        self.insert_shape(shape, handle)
+        self.insert_rounded_shape(shape, handle)
     }
 
     #[cfg(feature = "dim3")]
     pub fn shape_get_cylinder(&self, shape_handle: ShapeHandle) -> (Real, Real) {
-        if let Some(shape) = self.get_shape(shape_handle)
-            && let Some(shape) = shape.as_cylinder()
-        {
-            return (shape.half_height, shape.radius);
+        if let Some(shape) = self.get_shape(shape_handle) {
+            if let Some(shape) = shape.as_cylinder() {
+                return (shape.half_height, shape.radius);
+            }
+            if let Some(round) = shape.as_round_cylinder() {
+                let border_radius = round.border_radius;
+                let inner_shape = &round.inner_shape;
+                return (
+                    inner_shape.half_height + border_radius,
+                    inner_shape.radius + border_radius,
+                );
+            }
         }
         (0.0, 0.0)
     }
 
+    /// `heights` are row-major, `width` samples along x per row and `depth` rows along z. NaN
+    /// heights remove the cells around them, as do the set entries of `holes`, one per cell
+    /// and row-major as well, when it is not empty.
     #[cfg(feature = "dim3")]
     pub fn shape_create_heightmap(
         &mut self,
         heights: &[Real],
         width: i32,
         depth: i32,
+        holes: &[bool],
         handle: ShapeHandle,
     ) {
         use rapier::parry::utils::Array2;
         let width = width.max(0) as usize;
         let depth = depth.max(0) as usize;
-        if width == 0 || depth == 0 || heights.len() < width * depth {
+        if width < 2 || depth < 2 || heights.len() < width * depth {
             godot_error!(
                 "Heightmap requires width*depth ({}) heights, got {}",
                 width * depth,
@@ -328,36 +533,172 @@ impl PhysicsEngine {
             self.remove_shape(handle);
             return;
         }
-        let mut rotated_data = Vec::with_capacity(width * depth);
-        for j in 0..width {
+        let fill = heights
+            .iter()
+            .copied()
+            .filter(|h| !h.is_nan())
+            .reduce(Real::min)
+            .unwrap_or(0.0);
+        let heights_zx = Array2::from_fn(depth, width, |i, j| {
+            let height = heights[i * width + j];
+            if height.is_nan() { fill } else { height }
+        });
+        let mut shape = HeightField::with_flags(
+            heights_zx,
+            Vector::new(width as Real, 1.0, depth as Real),
+            HeightFieldFlags::FIX_INTERNAL_EDGES,
+        );
+        let cells_width = width - 1;
+        for i in 0..depth - 1 {
+            for j in 0..cells_width {
+                let corners = [(i, j), (i, j + 1), (i + 1, j), (i + 1, j + 1)];
+                if holes.get(i * cells_width + j).copied().unwrap_or(false)
+                    || corners.iter().any(|(i, j)| heights[i * width + j].is_nan())
+                {
+                    shape.set_cell_status(i, j, HeightFieldCellStatus::CELL_REMOVED);
+                }
+            }
+        }
+        self.insert_shape(SharedShape::new(shape), handle)
+    }
+
+    /// Heights row-major, the width and depth, and whether each cell is a hole.
+    #[cfg(feature = "dim3")]
+    pub fn shape_get_heightmap(
+        &self,
+        shape_handle: ShapeHandle,
+    ) -> (Vec<Real>, i32, i32, Vec<bool>) {
+        if let Some(shape) = self.get_shape(shape_handle)
+            && let Some(shape) = shape.as_heightfield()
+        {
+            let heights_zx = shape.heights();
+            let (depth, width) = (heights_zx.nrows(), heights_zx.ncols());
+            let mut heights = Vec::with_capacity(width * depth);
             for i in 0..depth {
-                rotated_data.push(heights[i * width + j]);
+                for j in 0..width {
+                    heights.push(heights_zx[(i, j)]);
+                }
             }
+            let mut holes = Vec::with_capacity((width - 1) * (depth - 1));
+            for i in 0..depth - 1 {
+                for j in 0..width - 1 {
+                    holes.push(
+                        shape
+                            .cell_status(i, j)
+                            .contains(HeightFieldCellStatus::CELL_REMOVED),
+                    );
+                }
+            }
+            return (heights, width as i32, depth as i32, holes);
         }
-        let rotated_heights = Array2::new(depth, width, rotated_data);
-        // The new dimensions after rotating the heightmap
-        let new_width = depth;
-        let new_depth = width;
-        // Create the shape with the rotated dimensions
-        let shape = SharedShape::heightfield_with_flags(
-            rotated_heights,
-            Vector::new(new_depth as Real, 1.0, new_width as Real),
-            HeightFieldFlags::FIX_INTERNAL_EDGES,
-        );
-        self.insert_shape(shape, handle)
+        (Vec::new(), 0, 0, Vec::new())
     }
 
+    /// Replaces the `width` by `depth` heights starting at column `x` and row `z`. A NaN height
+    /// makes a hole of the cells around it, and cells entirely inside the region with no NaN
+    /// corner are filled again. Returns `false` if the region is not inside the heightmap.
     #[cfg(feature = "dim3")]
-    pub fn shape_get_heightmap(&self, shape_handle: ShapeHandle) -> (Vec<Real>, i32, i32) {
+    pub fn shape_update_heightmap_region(
+        &mut self,
+        shape_handle: ShapeHandle,
+        x: i32,
+        z: i32,
+        width: i32,
+        depth: i32,
+        heights: &[Real],
+    ) -> bool {
+        let (x, z) = (x.max(0) as usize, z.max(0) as usize);
+        let (width, depth) = (width.max(0) as usize, depth.max(0) as usize);
+        let Some(shape) = self
+            .shapes
+            .get_mut(&shape_handle)
+            .and_then(|s| s.make_mut().as_heightfield_mut())
+        else {
+            return false;
+        };
+        let heights_zx = shape.heights();
+        if width == 0
+            || depth == 0
+            || heights.len() < width * depth
+            || x + width > heights_zx.ncols()
+            || z + depth > heights_zx.nrows()
+        {
+            return false;
+        }
+        let height_at = |i: usize, j: usize| -> Option<Real> {
+            let (i, j) = (i.checked_sub(z)?, j.checked_sub(x)?);
+            (i < depth && j < width).then(|| heights[i * width + j])
+        };
+        let statuses = shape.cells_statuses_mut();
+        let rows = z.saturating_sub(1)..(z + depth).min(statuses.nrows());
+        let cols = x.saturating_sub(1)..(x + width).min(statuses.ncols());
+        for i in rows {
+            for j in cols.clone() {
+                let corners =
+                    [(i, j), (i, j + 1), (i + 1, j), (i + 1, j + 1)].map(|(i, j)| height_at(i, j));
+                if corners.iter().any(|h| h.is_some_and(|h| h.is_nan())) {
+                    statuses[(i, j)].insert(HeightFieldCellStatus::CELL_REMOVED);
+                } else if corners.iter().all(Option::is_some) {
+                    statuses[(i, j)].remove(HeightFieldCellStatus::CELL_REMOVED);
+                }
+            }
+        }
+        // Keep the old height under a hole so the bounds do not move.
+        let changed = |i: usize, j: usize| {
+            let height = heights[i * width + j];
+            !height.is_nan() && height != shape.heights()[(z + i, x + j)]
+        };
+        if (0..depth).any(|i| (0..width).any(|j| changed(i, j))) {
+            // Parry has no way to write heights in place, they are only rebuilt when they move.
+            let mut heights_zx = shape.heights().clone();
+            for i in 0..depth {
+                for j in 0..width {
+                    let height = heights[i * width + j];
+                    if !height.is_nan() {
+                        heights_zx[(z + i, x + j)] = height;
+                    }
+                }
+            }
+            let mut new_shape = HeightField::with_flags(heights_zx, shape.scale(), shape.flags());
+            std::mem::swap(new_shape.cells_statuses_mut(), shape.cells_statuses_mut());
+            *shape = new_shape;
+        }
+        self.forget_scaled_shapes(shape_handle);
+        true
+    }
+
+    /// `spacing` is the horizontal distance between two consecutive heights, the heightfield is
+    /// centered on the origin.
+    #[cfg(feature = "dim2")]
+    pub fn shape_create_heightfield(
+        &mut self,
+        heights: &[Real],
+        spacing: Real,
+        handle: ShapeHandle,
+    ) {
+        if heights.len() < 2 {
+            godot_error!(
+                "Heightfield requires at least 2 heights, got {}",
+                heights.len()
+            );
+            self.remove_shape(handle);
+            return;
+        }
+        let width = spacing * (heights.len() - 1) as Real;
+        let shape = SharedShape::heightfield(heights.to_vec(), Vector::new(width, 1.0));
+        self.insert_shape(shape, handle)
+    }
+
+    #[cfg(feature = "dim2")]
+    pub fn shape_get_heightfield(&self, shape_handle: ShapeHandle) -> (Vec<Real>, Real) {
         if let Some(shape) = self.get_shape(shape_handle)
             && let Some(shape) = shape.as_heightfield()
         {
-            let scale = shape.scale();
-            let depth = scale.x as i32;
-            let width = scale.z as i32;
-            return (shape.heights().data().to_vec(), depth, width);
+            let heights = shape.heights();
+            let spacing = shape.scale().x / shape.num_cells() as Real;
+            return (heights.clone(), spacing);
         }
-        (Vec::new(), 0, 0)
+        (Vec::new(), 0.0)
     }
 
     #[cfg(feature = "dim2")]
@@ -419,14 +760,17 @@ impl PhysicsEngine {
             self.remove_shape(handle);
             return;
         }
+        let mut flags = self
+            .shape_get_trimesh_flags(handle)
+            .unwrap_or(crate::servers::rapier_project_settings::motion_settings().trimesh_flags);
         // When backface collision is enabled the mesh must collide on both sides of its
         // faces, so the ORIENTED flag (which constrains contacts to the outward face cone)
-        // must be dropped. Otherwise keep FIX_INTERNAL_EDGES for robust one-sided contacts.
-        let flags = if backface_collision {
-            TriMeshFlags::MERGE_DUPLICATE_VERTICES | TriMeshFlags::DELETE_DEGENERATE_TRIANGLES
-        } else {
-            TriMeshFlags::FIX_INTERNAL_EDGES
-        };
+        // must be dropped, and FIX_INTERNAL_EDGES with it since it relies on the orientation.
+        if backface_collision {
+            flags.remove(TriMeshFlags::ORIENTED | TriMeshFlags::FIX_INTERNAL_EDGES);
+            flags |=
+                TriMeshFlags::MERGE_DUPLICATE_VERTICES | TriMeshFlags::DELETE_DEGENERATE_TRIANGLES;
+        }
         let shape = SharedShape::trimesh_with_flags(points_vec, indices, flags);
         match shape {
             Ok(s) => self.insert_shape(s, handle),
@@ -463,6 +807,47 @@ impl PhysicsEngine {
         (&[], &[])
     }
 
+    pub fn shape_create_voxels(
+        &mut self,
+        voxel_size: Vector,
+        cells: &[IVector],
+        handle: ShapeHandle,
+    ) {
+        if cells.is_empty() {
+            godot_error!("Voxel shape requires at least one cell");
+            self.remove_shape(handle);
+            return;
+        }
+        let shape = SharedShape::voxels(voxel_size, cells);
+        self.insert_shape(shape, handle);
+    }
+
+    pub fn shape_get_voxels(&self, shape_handle: ShapeHandle) -> (Vector, Vec<IVector>) {
+        if let Some(shape) = self.get_shape(shape_handle)
+            && let Some(shape) = shape.as_voxels()
+        {
+            let cells = shape.voxels().map(|voxel| voxel.grid_coords).collect();
+            return (shape.voxel_size(), cells);
+        }
+        (Vector::ZERO, Vec::new())
+    }
+
+    /// Returns whether the cell was filled before the change.
+    pub fn shape_set_voxel(
+        &mut self,
+        shape_handle: ShapeHandle,
+        cell: IVector,
+        filled: bool,
+    ) -> bool {
+        self.forget_scaled_shapes(shape_handle);
+        if let Some(shape) = self.shapes.get_mut(&shape_handle)
+            && let Some(shape) = shape.make_mut().as_voxels_mut()
+        {
+            return !shape.set_voxel(cell, filled).is_empty();
+        }
+        false
+    }
+
     pub fn shape_create_separation_ray(
         &mut self,
         length: Real,
@@ -482,6 +867,200 @@ impl PhysicsEngine {
         (0.0, false)
     }
 
+    /// `None` follows the project setting.
+    pub fn shape_set_convex_decomposition(&mut self, handle: ShapeHandle, enabled: Option<bool>) {
+        match enabled {
+            Some(enabled) => {
+                self.convex_decomposition_overrides.insert(handle, enabled);
+            }
+            None => {
+                self.convex_decomposition_overrides.remove(&handle);
+            }
+        }
+    }
+
+    pub fn shape_get_convex_decomposition(&self, handle: ShapeHandle) -> Option<bool> {
+        self.convex_decomposition_overrides.get(&handle).copied()
+    }
+
+    /// `None` follows the project setting. Only trimeshes created afterwards use the flags.
+    #[cfg(feature = "dim3")]
+    pub fn shape_set_trimesh_flags(&mut self, handle: ShapeHandle, flags: Option<TriMeshFlags>) {
+        match flags {
+            Some(flags) => {
+                self.trimesh_flags_overrides.insert(handle, flags);
+            }
+            None => {
+                self.trimesh_flags_overrides.remove(&handle);
+            }
+        }
+    }
+
+    #[cfg(feature = "dim3")]
+    pub fn shape_get_trimesh_flags(&self, handle: ShapeHandle) -> Option<TriMeshFlags> {
+        self.trimesh_flags_overrides.get(&handle).copied()
+    }
+
+    /// Returns `false`, leaving the shape as it was, for an empty or nested compound.
+    #[cfg(feature = "dim3")]
+    pub fn shape_create_primitive(
+        &mut self,
+        primitive: &PrimitiveShape,
+        handle: ShapeHandle,
+    ) -> bool {
+        let Some(shape) = primitive_shape(primitive) else {
+            return false;
+        };
+        self.insert_rounded_shape(shape, handle);
+        self.primitives.insert(handle, primitive.clone());
+        true
+    }
+
+    #[cfg(feature = "dim3")]
+    pub fn shape_get_primitive(&self, handle: ShapeHandle) -> Option<&PrimitiveShape> {
+        self.primitives.get(&handle)
+    }
+
+    /// Records what an imported shape was built from, without rebuilding it.
+    #[cfg(feature = "dim3")]
+    pub fn shape_set_primitive(&mut self, handle: ShapeHandle, primitive: Option<PrimitiveShape>) {
+        match primitive {
+            Some(primitive) => {
+                self.primitives.insert(handle, primitive);
+            }
+            None => {
+                self.primitives.remove(&handle);
+            }
+        }
+    }
+
+    /// Boxes, convex shapes, cylinders and primitives created afterwards get a rounded border
+    /// of `radius`, taken from inside them so they keep their size. `0` keeps them sharp.
+    pub fn shape_set_border_radius(&mut self, handle: ShapeHandle, radius: Real) {
+        if radius > 0.0 {
+            self.border_radii.insert(handle, radius);
+        } else {
+            self.border_radii.remove(&handle);
+        }
+    }
+
+    pub fn shape_get_border_radius(&self, handle: ShapeHandle) -> Real {
+        self.border_radii.get(&handle).copied().unwrap_or(0.0)
+    }
+
+    fn insert_rounded_shape(&mut self, shape: SharedShape, handle: ShapeHandle) {
+        let shape = round_shape_inside(&shape, self.shape_get_border_radius(handle));
+        self.insert_shape(shape, handle);
+    }
+
+    /// The convex parts of a concave shape, for colliders of dynamic bodies. `None` when the
+    /// shape is not concave or decomposition is disabled for it. Decomposing is slow, so the
+    /// result is kept until the shape is replaced.
+    pub fn shape_get_convex_parts(&mut self, handle: ShapeHandle) -> Option<SharedShape> {
+        let shape = self.get_shape(handle)?;
+        #[cfg(feature = "dim2")]
+        let (vertices, indices) = {
+            let polyline = shape.as_polyline()?;
+            (polyline.vertices(), polyline.indices())
+        };
+        #[cfg(feature = "dim3")]
+        let (vertices, indices) = {
+            let trimesh = shape.as_trimesh()?;
+            (trimesh.vertices(), trimesh.indices())
+        };
+        let enabled = self.shape_get_convex_decomposition(handle).unwrap_or(
+            crate::servers::rapier_project_settings::motion_settings().convex_decomposition,
+        );
+        if !enabled {
+            return None;
+        }
+        if let Some(parts) = self.convex_decompositions.get(&handle) {
+            return Some(parts.clone());
+        }
+        #[cfg(feature = "dim2")]
+        let parts = ColliderBuilder::convex_decomposition(vertices, indices).shape;
+        #[cfg(feature = "dim3")]
+        let parts = SharedShape::convex_decomposition(vertices, indices);
+        self.convex_decompositions.insert(handle, parts.clone());
+        Some(parts)
+    }
+
+    /// The shape of `shape_info` scaled and skewed as [`scale_shape`] does. Scaling rebuilds
+    /// circles and capsules from polylines, so the copies are kept per shape until it changes.
+    pub fn get_scaled_shape(&self, shape_info: ShapeInfo) -> Option<SharedShape> {
+        let shape = self.get_shape(shape_info.handle)?;
+        // The same checks `skew_shape` and `scale_shape` use to leave a shape as is, so
+        // near-identity transforms do not fill the cache.
+        #[cfg(feature = "dim2")]
+        let unskewed = shape_info.skew.is_zero_approx();
+        #[cfg(feature = "dim3")]
+        let unskewed = shape_info.skew == Mat3::IDENTITY;
+        if unskewed
+            && (shape_info.scale - Vector::ONE)
+                .length_squared()
+                .is_zero_approx()
+        {
+            return Some(shape.clone());
+        }
+        let key = ScaleKey::new(&shape_info);
+        let mut scaled_shapes = self
+            .scaled_shapes
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let scaled = scaled_shapes.entry(shape_info.handle).or_default();
+        if let Some((_, shape)) = scaled.iter().find(|(scaled_key, _)| *scaled_key == key) {
+            return Some(shape.clone());
+        }
+        if scaled.len() >= MAX_SCALED_SHAPES {
+            scaled.remove(0);
+        }
+        let shape = scale_shape(shape, shape_info);
+        scaled.push((key, shape.clone()));
+        Some(shape)
+    }
+
+    /// An already stored shape with the same content as `shape`, or `shape` itself, which is
+    /// then remembered for the next ones.
+    pub fn share_shape_content(&mut self, shape: SharedShape) -> SharedShape {
+        let Some(hash) = shape_content_hash(&shape) else {
+            return shape;
+        };
+        let shapes = self.shape_contents.entry(hash).or_default();
+        shapes.retain(|shared| shared.strong_count() > 0);
+        for shared in shapes.iter() {
+            if let Some(shared) = shared.upgrade().map(SharedShape)
+                && same_shape_content(&shared, &shape)
+            {
+                return shared;
+            }
+        }
+        shapes.push(Arc::downgrade(&shape.0));
+        shape
+    }
+
+    /// Drops the content entries no shape uses anymore after `shape` is removed.
+    pub fn forget_shape_content(&mut self, shape: SharedShape) {
+        let Some(hash) = shape_content_hash(&shape) else {
+            return;
+        };
+        drop(shape);
+        if let Some(shapes) = self.shape_contents.get_mut(&hash) {
+            shapes.retain(|shared| shared.strong_count() > 0);
+            if shapes.is_empty() {
+                self.shape_contents.remove(&hash);
+            }
+        }
+    }
+
+    /// How many distinct shapes back the stored shape handles.
+    pub fn unique_shape_count(&self) -> usize {
+        self.shapes
+            .values()
+            .map(|shape| Arc::as_ptr(&shape.0).cast::<()>())
+            .collect::<hashbrown::HashSet<_>>()
+            .len()
+    }
+
     pub fn shape_get_aabb(&self, handle: ShapeHandle) -> rapier::prelude::Aabb {
         if let Some(shape) = self.get_shape(handle) {
             return shape.compute_local_aabb();
@@ -490,7 +1069,13 @@ impl PhysicsEngine {
     }
 
     pub fn shape_destroy(&mut self, shape_handle: ShapeHandle) {
-        self.remove_shape(shape_handle)
+        self.remove_shape(shape_handle);
+        self.convex_decomposition_overrides.remove(&shape_handle);
+        #[cfg(feature = "dim3")]
+        self.trimesh_flags_overrides.remove(&shape_handle);
+        self.border_radii.remove(&shape_handle);
+        #[cfg(feature = "dim3")]
+        self.primitives.remove(&shape_handle);
     }
 }
 #[cfg(all(test, feature = "dim2"))]
@@ -548,4 +1133,330 @@ mod tests {
             assert!(hull_points.contains(&point));
         }
     }
+    #[test]
+    fn convex_parts_are_cached_until_shape_is_replaced() {
+        let mut physics_engine = PhysicsEngine::default();
+        let handle = 1;
+        // An L-shaped outline given as separate segments, the way ConcavePolygonShape2D is.
+        let corners = [
+            Vector::new(0.0, 0.0),
+            Vector::new(2.0, 0.0),
+            Vector::new(2.0, 1.0),
+            Vector::new(1.0, 1.0),
+            Vector::new(1.0, 2.0),
+            Vector::new(0.0, 2.0),
+        ];
+        let points: Vec<Vector> = (0..corners.len())
+            .flat_map(|i| [corners[i], corners[(i + 1) % corners.len()]])
+            .collect();
+        let indices: Vec<[u32; 2]> = (0..corners.len() as u32)
+            .map(|i| [2 * i, 2 * i + 1])
+            .collect();
+        physics_engine.insert_shape(SharedShape::polyline(points, Some(indices)), handle);
+        physics_engine.shape_set_convex_decomposition(handle, Some(false));
+        assert!(physics_engine.shape_get_convex_parts(handle).is_none());
+        physics_engine.shape_set_convex_decomposition(handle, Some(true));
+        let parts = physics_engine.shape_get_convex_parts(handle).unwrap();
+        assert!(parts.as_compound().is_some());
+        assert!(physics_engine.convex_decompositions.contains_key(&handle));
+        physics_engine.shape_create_circle(1.0, handle);
+        assert!(!physics_engine.convex_decompositions.contains_key(&handle));
+        assert!(physics_engine.shape_get_convex_parts(handle).is_none());
+    }
+}
+#[cfg(all(test, feature = "dim3"))]
+mod tests {
+    use super::*;
+    #[test]
+    fn heightmap_region_update_keeps_holes_outside_region() {
+        let mut physics_engine = PhysicsEngine::default();
+        let handle = 1;
+        // A flat 4x3 heightmap with the last cell removed through the mask.
+        let mut heights = vec![0.0; 12];
+        heights[1] = Real::NAN;
+        let mut holes = vec![false; 6];
+        holes[5] = true;
+        physics_engine.shape_create_heightmap(&heights, 4, 3, &holes, handle);
+        let (data, width, depth, data_holes) = physics_engine.shape_get_heightmap(handle);
+        assert_eq!((width, depth), (4, 3));
+        assert!(data.iter().all(|h| *h == 0.0));
+        assert_eq!(data_holes, [true, true, false, false, false, true]);
+        // Filling the NaN height back in restores both cells around it.
+        assert!(physics_engine.shape_update_heightmap_region(handle, 0, 0, 3, 2, &[1.0; 6]));
+        let (data, _, _, data_holes) = physics_engine.shape_get_heightmap(handle);
+        assert_eq!(&data[..4], [1.0, 1.0, 1.0, 0.0]);
+        assert_eq!(data_holes, [false, false, false, false, false, true]);
+        // A NaN in the region removes the cells touching it, inside the region or not.
+        assert!(physics_engine.shape_update_heightmap_region(handle, 2, 1, 1, 1, &[Real::NAN]));
+        let (_, _, _, data_holes) = physics_engine.shape_get_heightmap(handle);
+        assert_eq!(data_holes, [false, true, true, false, true, true]);
+        assert!(!physics_engine.shape_update_heightmap_region(handle, 3, 2, 2, 1, &[0.0; 2]));
+    }
+    #[test]
+    fn trimesh_flags_override_applies_and_survives_mirrored_scale() {
+        let mut physics_engine = PhysicsEngine::default();
+        let handle = 1;
+        // Two triangles of a unit square sharing the diagonal.
+        let points = vec![
+            Vector::new(0.0, 0.0, 0.0),
+            Vector::new(1.0, 0.0, 0.0),
+            Vector::new(1.0, 0.0, 1.0),
+            Vector::new(0.0, 0.0, 1.0),
+        ];
+        let indices = vec![[0, 2, 1], [0, 3, 2]];
+        physics_engine.shape_set_trimesh_flags(handle, Some(TriMeshFlags::FIX_INTERNAL_EDGES));
+        physics_engine.shape_create_concave_polyline(&points, Some(indices.clone()), true, handle);
+        let trimesh = physics_engine
+            .get_shape(handle)
+            .unwrap()
+            .as_trimesh()
+            .unwrap();
+        assert!(!trimesh.flags().contains(TriMeshFlags::ORIENTED));
+        assert!(!trimesh.flags().contains(TriMeshFlags::FIX_INTERNAL_EDGES));
+        physics_engine.shape_create_concave_polyline(&points, Some(indices), false, handle);
+        let shape = physics_engine.get_shape(handle).unwrap().clone();
+        assert_eq!(
+            shape.as_trimesh().unwrap().flags(),
+            TriMeshFlags::FIX_INTERNAL_EDGES
+        );
+        let mut shape_info = shape_info_from_body_shape(handle, Transform::IDENTITY);
+        shape_info.scale = Vector::new(1.0, -1.0, 1.0);
+        let scaled = scale_shape(&shape, shape_info);
+        let scaled = scaled.as_trimesh().unwrap();
+        assert_eq!(scaled.flags(), TriMeshFlags::FIX_INTERNAL_EDGES);
+        // The mirrored mesh is flipped back so its faces still point outwards.
+        assert!(scaled.triangle(0).normal().unwrap().y < 0.0);
+        physics_engine.shape_destroy(handle);
+        assert!(physics_engine.shape_get_trimesh_flags(handle).is_none());
+    }
+    #[test]
+    fn trimesh_without_override_uses_default_flags() {
+        let mut physics_engine = PhysicsEngine::default();
+        let handle = 1;
+        let points = vec![
+            Vector::new(0.0, 0.0, 0.0),
+            Vector::new(1.0, 0.0, 0.0),
+            Vector::new(0.0, 0.0, 1.0),
+        ];
+        physics_engine.shape_create_concave_polyline(&points, Some(vec![[0, 2, 1]]), false, handle);
+        let trimesh = physics_engine
+            .get_shape(handle)
+            .unwrap()
+            .as_trimesh()
+            .unwrap();
+        assert_eq!(trimesh.flags(), TriMeshFlags::FIX_INTERNAL_EDGES);
+    }
+    #[test]
+    fn border_radius_rounds_box_and_scales_with_it() {
+        let mut physics_engine = PhysicsEngine::default();
+        let handle = 1;
+        physics_engine.shape_set_border_radius(handle, 0.1);
+        physics_engine.shape_create_box(Vector::new(2.0, 2.0, 2.0), handle);
+        let shape = physics_engine.get_shape(handle).unwrap().clone();
+        assert_eq!(shape.shape_type(), ShapeType::RoundCuboid);
+        assert_eq!(
+            physics_engine.shape_get_box_size(handle),
+            Vector::new(1.0, 1.0, 1.0)
+        );
+        let mut shape_info = shape_info_from_body_shape(handle, Transform::IDENTITY);
+        shape_info.scale = Vector::new(2.0, 3.0, 2.0);
+        let scaled = scale_shape(&shape, shape_info);
+        let aabb = scaled.compute_local_aabb();
+        assert!((aabb.maxs - Vector::new(2.0, 2.9, 2.0)).length() < 1e-5);
+        let scaled = scaled.as_round_cuboid().unwrap();
+        assert!((scaled.inner_shape.half_extents - Vector::new(1.8, 2.7, 1.8)).length() < 1e-5);
+        assert!((scaled.border_radius - 0.2).abs() < 1e-6);
+        // A border wider than the box only rounds it fully.
+        physics_engine.shape_set_border_radius(handle, 2.0);
+        physics_engine.shape_create_box(Vector::new(2.0, 1.0, 2.0), handle);
+        let shape = physics_engine.get_shape(handle).unwrap();
+        assert_eq!(shape.as_round_cuboid().unwrap().border_radius, 0.5);
+        assert_eq!(shape.compute_local_aabb().maxs, Vector::new(1.0, 0.5, 1.0));
+        physics_engine.shape_set_border_radius(handle, 0.1);
+        let points = [
+            Vector::new(-1.0, -1.0, -1.0),
+            Vector::new(1.0, -1.0, -1.0),
+            Vector::new(0.0, 1.0, -1.0),
+            Vector::new(0.0, 0.0, 1.0),
+        ];
+        assert!(physics_engine.shape_create_convex_polyline(&points.to_vec(), handle));
+        let aabb = physics_engine.shape_get_aabb(handle);
+        assert!(aabb.mins.x >= -1.0 - 1e-5 && aabb.maxs.y <= 1.0 + 1e-5);
+        assert!(aabb.maxs.z <= 1.0 + 1e-5);
+        for (point, expected) in physics_engine
+            .shape_get_convex_polyline_points(handle)
+            .iter()
+            .zip(points)
+        {
+            assert!((*point - expected).length() < 1e-4);
+        }
+        physics_engine.shape_set_border_radius(handle, 0.0);
+        physics_engine.shape_create_cylinder(1.0, 0.5, handle);
+        let shape = physics_engine.get_shape(handle).unwrap();
+        assert_eq!(shape.shape_type(), ShapeType::Cylinder);
+        physics_engine.shape_set_border_radius(handle, 0.1);
+        physics_engine.shape_create_cylinder(1.0, 0.5, handle);
+        let (half_height, radius) = physics_engine.shape_get_cylinder(handle);
+        assert!((half_height - 1.0).abs() < 1e-6 && (radius - 0.5).abs() < 1e-6);
+        let aabb = physics_engine.shape_get_aabb(handle);
+        assert!((aabb.maxs - Vector::new(0.5, 1.0, 0.5)).length() < 1e-5);
+        physics_engine.shape_destroy(handle);
+        assert_eq!(physics_engine.shape_get_border_radius(handle), 0.0);
+    }
+    #[test]
+    fn scaled_shapes_are_reused_until_shape_changes() {
+        let mut physics_engine = PhysicsEngine::default();
+        let handle = 1;
+        physics_engine.shape_create_circle(1.0, handle);
+        let mut shape_info = shape_info_from_body_shape(handle, Transform::IDENTITY);
+        shape_info.scale = Vector::new(1.0, 2.0, 1.0);
+        let first = physics_engine.get_scaled_shape(shape_info).unwrap();
+        let second = physics_engine.get_scaled_shape(shape_info).unwrap();
+        assert!(std::sync::Arc::ptr_eq(&first.0, &second.0));
+        physics_engine.shape_create_circle(2.0, handle);
+        let third = physics_engine.get_scaled_shape(shape_info).unwrap();
+        assert!(!std::sync::Arc::ptr_eq(&first.0, &third.0));
+        assert!((third.compute_local_aabb().maxs.y - 4.0).abs() < 1e-3);
+        // A scale that is one up to rounding uses the shape itself.
+        shape_info.scale = Vector::new(1.0, 1.0 + 1e-7, 1.0);
+        let unscaled = physics_engine.get_scaled_shape(shape_info).unwrap();
+        assert!(std::sync::Arc::ptr_eq(
+            &unscaled.0,
+            &physics_engine.get_shape(handle).unwrap().0
+        ));
+        assert_eq!(
+            physics_engine.scaled_shapes.lock().unwrap()[&handle].len(),
+            1
+        );
+        physics_engine.shape_destroy(handle);
+        assert!(physics_engine.get_scaled_shape(shape_info).is_none());
+        assert!(physics_engine.scaled_shapes.lock().unwrap().is_empty());
+    }
+    #[test]
+    fn identical_convex_hulls_share_one_shape() {
+        let mut physics_engine = PhysicsEngine::default();
+        let points = vec![
+            Vector::ZERO,
+            Vector::new(1.0, 0.0, 0.0),
+            Vector::new(0.0, 1.0, 0.0),
+            Vector::new(0.0, 0.0, 1.0),
+        ];
+        assert!(physics_engine.shape_create_convex_polyline(&points, 1));
+        assert!(physics_engine.shape_create_convex_polyline(&points, 2));
+        let mut other_points = points.clone();
+        other_points[3].z = 2.0;
+        assert!(physics_engine.shape_create_convex_polyline(&other_points, 3));
+        let first = physics_engine.get_shape(1).unwrap();
+        assert!(Arc::ptr_eq(
+            &first.0,
+            &physics_engine.get_shape(2).unwrap().0
+        ));
+        assert!(!Arc::ptr_eq(
+            &first.0,
+            &physics_engine.get_shape(3).unwrap().0
+        ));
+        assert_eq!(physics_engine.shapes.len(), 3);
+        assert_eq!(physics_engine.unique_shape_count(), 2);
+        // Replacing the only shape with some content forgets that content.
+        assert_eq!(physics_engine.shape_contents.len(), 2);
+        physics_engine.shape_create_box(Vector::ONE, 3);
+        assert_eq!(physics_engine.shape_contents.len(), 1);
+        for handle in 1..=3 {
+            physics_engine.shape_destroy(handle);
+        }
+        assert!(physics_engine.shape_contents.is_empty());
+    }
+    #[test]
+    fn sheared_transform_shears_box_corners() {
+        let mut physics_engine = PhysicsEngine::default();
+        let handle = 1;
+        physics_engine.shape_create_box(Vector::new(2.0, 2.0, 2.0), handle);
+        let basis = godot::builtin::Basis::from_cols(
+            godot::builtin::Vector3::new(1.0, 0.0, 0.0),
+            godot::builtin::Vector3::new(0.5, 1.0, 0.0),
+            godot::builtin::Vector3::new(0.0, 0.25, 2.0),
+        );
+        let shape_info = shape_info_from_body_shape(
+            handle,
+            Transform::new(basis, godot::builtin::Vector3::ZERO),
+        );
+        assert_ne!(shape_info.skew, Mat3::IDENTITY);
+        let scaled = physics_engine.get_scaled_shape(shape_info).unwrap();
+        let points = scaled.as_convex_polyhedron().unwrap().points();
+        assert_eq!(points.len(), 8);
+        for corner in [-1.0, 1.0].into_iter().flat_map(|x| {
+            [-1.0, 1.0]
+                .into_iter()
+                .flat_map(move |y| [-1.0, 1.0].map(|z| godot::builtin::Vector3::new(x, y, z)))
+        }) {
+            let expected = vector_to_rapier(basis * corner);
+            assert!(
+                points
+                    .iter()
+                    .any(|point| (shape_info.transform * *point - expected).length() < 1e-4)
+            );
+        }
+        // Rounded parts keep their border around the sheared inner shape, and flat parts are
+        // sheared too.
+        let origin = Pose::from_parts(Vector::ZERO, Rotation::default());
+        let compound = PrimitiveShape::Compound(vec![
+            (
+                origin,
+                PrimitiveShape::Cone {
+                    half_height: 1.0,
+                    radius: 0.5,
+                },
+            ),
+            (
+                origin,
+                PrimitiveShape::Segment(Vector::ZERO, Vector::new(0.0, 1.0, 0.0)),
+            ),
+        ]);
+        physics_engine.shape_set_border_radius(handle, 0.1);
+        assert!(physics_engine.shape_create_primitive(&compound, handle));
+        let shape = physics_engine.get_shape(handle).unwrap().clone();
+        let skewed = skew_shape(&shape, shape_info);
+        let parts = skewed.as_compound().unwrap().shapes();
+        assert_eq!(parts[0].1.shape_type(), ShapeType::RoundConvexPolyhedron);
+        let segment = parts[1].1.as_segment().unwrap();
+        assert_eq!(segment.b, shape_info.skew * Vector::new(0.0, 1.0, 0.0));
+    }
+    #[test]
+    fn primitive_shapes_build_and_reject_nested_compounds() {
+        let mut physics_engine = PhysicsEngine::default();
+        let handle = 1;
+        let cone = PrimitiveShape::Cone {
+            half_height: 1.0,
+            radius: 0.5,
+        };
+        assert!(physics_engine.shape_create_primitive(&cone, handle));
+        let shape = physics_engine.get_shape(handle).unwrap().clone();
+        let mut shape_info = shape_info_from_body_shape(handle, Transform::IDENTITY);
+        shape_info.scale = Vector::new(2.0, 2.0, 2.0);
+        let scaled = scale_shape(&shape, shape_info);
+        assert_eq!(scaled.as_cone().unwrap().half_height, 2.0);
+        let tapered = PrimitiveShape::Capsule {
+            a: Vector::ZERO,
+            b: Vector::new(0.0, 2.0, 0.0),
+            radius_a: 1.0,
+            radius_b: 0.5,
+        };
+        assert!(physics_engine.shape_create_primitive(&tapered, handle));
+        let aabb = physics_engine.shape_get_aabb(handle);
+        assert!((aabb.mins.y + 1.0).abs() < 1e-3);
+        assert!((aabb.maxs.y - 2.5).abs() < 1e-3);
+        let origin = Pose::from_parts(Vector::ZERO, Rotation::default());
+        let nested = PrimitiveShape::Compound(vec![(
+            origin,
+            PrimitiveShape::Compound(vec![(origin, cone)]),
+        )]);
+        assert!(!physics_engine.shape_create_primitive(&nested, handle));
+        assert!(
+            physics_engine
+                .get_shape(handle)
+                .unwrap()
+                .as_convex_polyhedron()
+                .is_some()
+        );
+    }
 }
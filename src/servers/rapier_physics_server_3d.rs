@@ -78,7 +78,7 @@ impl IPhysicsServer3DExtension for RapierPhysicsServer3D {
     }
 
     fn custom_shape_create(&mut self) -> Rid {
-        Rid::Invalid
+        self.implementation.custom_shape_create()
     }
 
     fn shape_set_data(&mut self, shape: Rid, data: Variant) {
@@ -1,4 +1,22 @@
 use godot::prelude::*;
+use rapier::prelude::JointAxesMask;
+use rapier::prelude::Pose;
+use rapier::prelude::RigidBodyHandle;
+
+use crate::joints::rapier_coupling_joint::RapierCouplingJoint;
+use crate::joints::rapier_coupling_joint::RapierCouplingKind;
+use crate::joints::rapier_joint::IRapierJoint;
+use crate::joints::rapier_joint::RapierJoint;
+use crate::joints::rapier_joint_base::RapierJointType;
+use crate::rapier_wrapper::convert::vector_to_godot;
+use crate::rapier_wrapper::joint::JointDebugInfo;
+use crate::rapier_wrapper::prelude::*;
+use crate::servers::rapier_physics_singleton::get_id_rid;
+use crate::servers::rapier_physics_singleton::insert_id_rid;
+use crate::servers::rapier_physics_singleton::physics_data;
+use crate::servers::try_rapier_physics_server;
+use crate::spaces::rapier_space::RapierSpace;
+use crate::types::*;
 #[derive(GodotConvert, Var, Export, Debug, Clone, Copy, PartialEq)]
 #[godot(via = i32)]
 pub enum RapierBodyParam {
@@ -20,6 +38,172 @@ impl RapierBodyParam {
         }
     }
 }
+#[derive(GodotConvert, Var, Export, Debug, Clone, Copy, PartialEq)]
+#[godot(via = i32)]
+pub enum RapierSpringParam {
+    RestLength,
+    Stiffness,
+    Damping,
+    MinLength,
+    MaxLength,
+}
+impl RapierSpringParam {
+    pub fn from_i32(value: i32) -> RapierSpringParam {
+        match value {
+            0 => RapierSpringParam::RestLength,
+            1 => RapierSpringParam::Stiffness,
+            2 => RapierSpringParam::Damping,
+            3 => RapierSpringParam::MinLength,
+            4 => RapierSpringParam::MaxLength,
+            _ => RapierSpringParam::RestLength,
+        }
+    }
+}
+#[derive(GodotConvert, Var, Export, Debug, Clone, Copy, PartialEq)]
+#[godot(via = i32)]
+pub enum RapierShapeParam {
+    ConvexDecomposition,
+    #[cfg(feature = "dim3")]
+    TrimeshFlags,
+}
+impl RapierShapeParam {
+    pub fn from_i32(value: i32) -> RapierShapeParam {
+        match value {
+            0 => RapierShapeParam::ConvexDecomposition,
+            #[cfg(feature = "dim3")]
+            1 => RapierShapeParam::TrimeshFlags,
+            _ => RapierShapeParam::ConvexDecomposition,
+        }
+    }
+}
+/// Puts `new_joint` under `joint` the way the server's `joint_make_*` functions do: it takes
+/// over the settings and id of the joint it replaces, which is destroyed, and its id is
+/// registered for its RID.
+pub fn replace_joint(joint: Rid, mut new_joint: RapierJoint) {
+    let physics_data = physics_data();
+    if let Some(mut prev_joint) = physics_data.joints.remove(&joint) {
+        // The new joint keeps the kind it was built as, couplings are never multibody joints.
+        let joint_type = new_joint.get_base().get_joint_type();
+        new_joint
+            .get_mut_base()
+            .copy_settings_from(prev_joint.get_base(), &mut physics_data.physics_engine);
+        new_joint.get_mut_base().set_joint_type(joint_type);
+        prev_joint
+            .get_mut_base()
+            .destroy_joint(&mut physics_data.physics_engine);
+    }
+    insert_id_rid(new_joint.get_base().get_id(), joint, &mut physics_data.ids);
+    RapierSpace::update_breakable_joint(
+        new_joint.get_base().get_id(),
+        Some(new_joint.get_base()),
+        &mut physics_data.spaces,
+        &physics_data.ids,
+    );
+    physics_data.joints.insert(joint, new_joint);
+}
+/// Replaces `joint` with a coupling between `body_a` and `body_b`, used by the `joint_make_gear`,
+/// `joint_make_rack_and_pinion` and `joint_make_pulley` functions.
+pub fn make_coupling_joint(
+    joint: Rid,
+    kind: RapierCouplingKind,
+    axis_a: Vector,
+    axis_b: Vector,
+    ratio: real,
+    body_a: Rid,
+    body_b: Rid,
+) {
+    let physics_data = physics_data();
+    let Some(mut physics_singleton) = try_rapier_physics_server() else {
+        return;
+    };
+    if let Some(body_a) = physics_data.collision_objects.get(&body_a)
+        && let Some(body_b) = physics_data.collision_objects.get(&body_b)
+    {
+        let id = physics_singleton
+            .bind_mut()
+            .implementation
+            .joint_id(joint, &physics_data.joints);
+        let new_joint = RapierJoint::RapierCouplingJoint(RapierCouplingJoint::new(
+            id,
+            joint,
+            kind,
+            axis_a,
+            axis_b,
+            ratio,
+            body_a,
+            body_b,
+            &mut physics_data.physics_engine,
+        ));
+        replace_joint(joint, new_joint);
+        // Gears and pulleys may touch, so coupled bodies keep colliding whatever the joint they
+        // replace was set to.
+        if let Some(new_joint) = physics_data.joints.get_mut(&joint) {
+            new_joint
+                .get_mut_base()
+                .disable_collisions_between_bodies(false, &mut physics_data.physics_engine);
+        }
+    }
+}
+/// Result dictionary of `joint_get_debug_info`.
+pub fn joint_debug_info_to_dictionary(
+    info: &JointDebugInfo,
+    body_a: Rid,
+    body_b: Rid,
+) -> VarDictionary {
+    let frame_to_godot = |frame: Pose| {
+        transform_update(
+            &Transform::IDENTITY,
+            frame.rotation,
+            vector_to_godot(frame.translation),
+        )
+    };
+    let mut dictionary = VarDictionary::new();
+    dictionary.set("body_a", body_a);
+    dictionary.set("body_b", body_b);
+    dictionary.set("frame_a", frame_to_godot(info.frame_1));
+    dictionary.set("frame_b", frame_to_godot(info.frame_2));
+    let mut axes = Array::<VarDictionary>::new();
+    for axis in 0..info.positions.len() {
+        let mask = JointAxesMask::from_bits_truncate(1 << axis);
+        let mut axis_info = VarDictionary::new();
+        axis_info.set("position", info.positions[axis]);
+        axis_info.set("locked", info.locked_axes.contains(mask));
+        axis_info.set("limited", info.limit_axes.contains(mask));
+        axis_info.set("limit_min", info.limits[axis][0]);
+        axis_info.set("limit_max", info.limits[axis][1]);
+        axis_info.set("motor_enabled", info.motor_axes.contains(mask));
+        axis_info.set("motor_target_position", info.motor_target_positions[axis]);
+        axis_info.set("motor_target_velocity", info.motor_target_velocities[axis]);
+        axes.push(&axis_info);
+    }
+    dictionary.set("axes", &axes);
+    dictionary
+}
+/// Joints attached to the body with `body_handle` in `space_handle`, each with the body on its
+/// other end, found from the joints rapier links to the body. Covers impulse, multibody and
+/// coupling joints.
+pub fn body_joint_links(
+    space_handle: WorldHandle,
+    body_handle: RigidBodyHandle,
+) -> Vec<(Rid, RigidBodyHandle)> {
+    let physics_data = physics_data();
+    physics_data
+        .physics_engine
+        .body_get_attached_joints(space_handle, body_handle)
+        .into_iter()
+        .filter_map(|(handle, other_body, user_data)| {
+            let rid = get_id_rid(user_data.part1, &physics_data.ids);
+            // The id only leads to the RID, the handle checks the joint under it is this one.
+            let joint = physics_data.joints.get(&rid)?.get_base();
+            let joint_handle = joint.get_handle();
+            (joint.get_space_id() == space_handle
+                && joint_handle.index == handle.index
+                && (joint_handle.joint_type == RapierJointType::Impulse)
+                    == (handle.joint_type == RapierJointType::Impulse))
+                .then_some((rid, other_body))
+        })
+        .collect()
+}
 #[macro_export]
 macro_rules! make_rapier_server_godot_impl {
     ($class: ident) => {
@@ -37,6 +221,7 @@ macro_rules! make_rapier_server_godot_impl {
         use $crate::joints::rapier_joint::RapierJoint;
         use $crate::joints::rapier_joint_base::RapierJointType;
         use $crate::servers::rapier_physics_server_extra::RapierBodyParam;
+        use $crate::servers::rapier_physics_server_extra::RapierShapeParam;
         use $crate::servers::try_rapier_physics_server;
         #[godot_api]
         impl $class {
@@ -50,6 +235,26 @@ macro_rules! make_rapier_server_godot_impl {
             pub const BODY_PARAM_SOFT_CCD: i32 = 2;
             #[constant]
             pub const BODY_PARAM_ADDITIONAL_SOLVER_ITERATIONS: i32 = 4;
+            #[cfg(feature = "dim3")]
+            #[constant]
+            pub const SPRING_PARAM_REST_LENGTH: i32 = 0;
+            #[cfg(feature = "dim3")]
+            #[constant]
+            pub const SPRING_PARAM_STIFFNESS: i32 = 1;
+            #[cfg(feature = "dim3")]
+            #[constant]
+            pub const SPRING_PARAM_DAMPING: i32 = 2;
+            #[cfg(feature = "dim3")]
+            #[constant]
+            pub const SPRING_PARAM_MIN_LENGTH: i32 = 3;
+            #[cfg(feature = "dim3")]
+            #[constant]
+            pub const SPRING_PARAM_MAX_LENGTH: i32 = 4;
+            #[constant]
+            pub const SHAPE_PARAM_CONVEX_DECOMPOSITION: i32 = 0;
+            #[cfg(feature = "dim3")]
+            #[constant]
+            pub const SHAPE_PARAM_TRIMESH_FLAGS: i32 = 1;
             #[constant]
             pub const JOINT_TYPE: i32 = 0;
             #[constant]
@@ -98,6 +303,63 @@ macro_rules! make_rapier_server_godot_impl {
                 0.0.to_variant()
             }
 
+            #[func]
+            /// Get the joints attached to [param body], across impulse, multibody and coupling
+            /// joints.
+            pub fn body_get_joints(body: Rid) -> Array<Rid> {
+                let physics_data = physics_data();
+                let mut joints = Array::new();
+                if let Some(body) = physics_data.collision_objects.get(&body) {
+                    for (joint, _) in $crate::servers::rapier_physics_server_extra::body_joint_links(
+                        body.get_base().get_space_id(),
+                        body.get_base().get_body_handle(),
+                    ) {
+                        joints.push(joint);
+                    }
+                }
+                joints
+            }
+
+            #[func]
+            /// Get the bodies linked to [param body] by a joint, not including [param body]
+            /// itself. If [param recursive] is [code]true[/code], follows joints from body to body
+            /// and returns the whole connected assembly.
+            pub fn body_get_connected_bodies(body: Rid, recursive: bool) -> Array<Rid> {
+                use $crate::bodies::rapier_collision_object_base::RapierCollisionObjectBase;
+                let physics_data = physics_data();
+                let mut bodies = Array::new();
+                let Some(body) = physics_data.collision_objects.get(&body) else {
+                    return bodies;
+                };
+                let space_handle = body.get_base().get_space_id();
+                let body_handle = body.get_base().get_body_handle();
+                let mut visited = std::collections::HashSet::from([body_handle]);
+                let mut pending = vec![body_handle];
+                while let Some(current) = pending.pop() {
+                    for (_, other_body) in
+                        $crate::servers::rapier_physics_server_extra::body_joint_links(
+                            space_handle,
+                            current,
+                        )
+                    {
+                        if !visited.insert(other_body) {
+                            continue;
+                        }
+                        if recursive {
+                            pending.push(other_body);
+                        }
+                        let (rid, _) = RapierCollisionObjectBase::get_collider_user_data(
+                            &physics_data
+                                .physics_engine
+                                .body_get_user_data(space_handle, other_body),
+                            &physics_data.ids,
+                        );
+                        bodies.push(rid);
+                    }
+                }
+                bodies
+            }
+
             #[func]
             /// Set the contact force magnitude a contact must exceed before it is reported.
             /// Weaker contacts are dropped inside the solver, which is cheaper than filtering
@@ -254,6 +516,541 @@ macro_rules! make_rapier_server_godot_impl {
                 false
             }
 
+            #[func]
+            /// Get what [param body] overlaps right now, and by how much, measured on its
+            /// colliders where they sit in the space. Solid colliders within [param margin] are
+            /// reported too, with a negative depth. Returns at most [param max_results]
+            /// dictionaries with [code]rid[/code], [code]collider_id[/code], [code]shape[/code],
+            /// [code]local_shape[/code], [code]point[/code], [code]collider_point[/code],
+            /// [code]normal[/code] and [code]depth[/code]. The normal points out of the other
+            /// collider, so moving [param body] by [code]normal * depth[/code] separates the pair.
+            pub fn body_get_penetrations(
+                body: Rid,
+                margin: real,
+                max_results: i32,
+            ) -> Array<VarDictionary> {
+                use $crate::bodies::rapier_collision_object_base::RapierCollisionObjectBase;
+                let mut results = Array::new();
+                let physics_data = physics_data();
+                if let Some(body) = physics_data.collision_objects.get(&body)
+                    && let Some(body) = body.get_body()
+                {
+                    let penetrations = physics_data.physics_engine.body_get_penetrations(
+                        body.get_base().get_space_id(),
+                        body.get_base().get_body_handle(),
+                        margin.max(0.0),
+                        max_results.max(0) as usize,
+                    );
+                    for penetration in penetrations {
+                        let (rid, shape) = RapierCollisionObjectBase::get_collider_user_data(
+                            &penetration.user_data,
+                            &physics_data.ids,
+                        );
+                        let (_, local_shape) = RapierCollisionObjectBase::get_collider_user_data(
+                            &penetration.local_user_data,
+                            &physics_data.ids,
+                        );
+                        let collider_id = physics_data
+                            .collision_objects
+                            .get(&rid)
+                            .map(|object| object.get_base().get_instance_id())
+                            .unwrap_or_default();
+                        let mut dictionary = VarDictionary::new();
+                        dictionary.set("rid", rid);
+                        dictionary.set("collider_id", collider_id as i64);
+                        dictionary.set("shape", shape as i64);
+                        dictionary.set("local_shape", local_shape as i64);
+                        dictionary.set(
+                            "point",
+                            $crate::rapier_wrapper::convert::vector_to_godot(
+                                penetration.pixel_point,
+                            ),
+                        );
+                        dictionary.set(
+                            "collider_point",
+                            $crate::rapier_wrapper::convert::vector_to_godot(
+                                penetration.pixel_collider_point,
+                            ),
+                        );
+                        dictionary.set(
+                            "normal",
+                            $crate::rapier_wrapper::convert::vector_to_godot(penetration.normal),
+                        );
+                        dictionary.set("depth", penetration.depth);
+                        results.push(&dictionary);
+                    }
+                }
+                results
+            }
+
+            #[func]
+            /// Predict whether [param body_a] and [param body_b] will touch within the next
+            /// [param max_time] seconds if both keep their current linear and angular velocity.
+            /// Returns a dictionary with [code]collided[/code], [code]time[/code] in seconds,
+            /// and the world-space [code]point_a[/code], [code]point_b[/code],
+            /// [code]normal_a[/code] and [code]normal_b[/code] at the moment of impact.
+            /// Bodies already touching report a [code]time[/code] of [code]0.0[/code].
+            pub fn bodies_time_of_impact(
+                body_a: Rid,
+                body_b: Rid,
+                max_time: real,
+            ) -> VarDictionary {
+                let physics_data = physics_data();
+                let mut result = Default::default();
+                if let Some(object_a) = physics_data.collision_objects.get(&body_a)
+                    && let Some(object_b) = physics_data.collision_objects.get(&body_b)
+                    && let Some(body_a) = object_a.get_body()
+                    && let Some(body_b) = object_b.get_body()
+                    && body_a.get_base().get_space_id() == body_b.get_base().get_space_id()
+                {
+                    result = physics_data.physics_engine.bodies_time_of_impact(
+                        body_a.get_base().get_space_id(),
+                        body_a.get_base().get_body_handle(),
+                        body_b.get_base().get_body_handle(),
+                        max_time.max(0.0),
+                    );
+                }
+                let mut dictionary = VarDictionary::new();
+                dictionary.set("collided", result.collided);
+                dictionary.set(
+                    "time",
+                    if result.collided {
+                        result.toi
+                    } else {
+                        max_time
+                    },
+                );
+                dictionary.set(
+                    "point_a",
+                    $crate::rapier_wrapper::convert::vector_to_godot(result.pixel_witness1),
+                );
+                dictionary.set(
+                    "point_b",
+                    $crate::rapier_wrapper::convert::vector_to_godot(result.pixel_witness2),
+                );
+                dictionary.set(
+                    "normal_a",
+                    $crate::rapier_wrapper::convert::vector_to_godot(result.normal1),
+                );
+                dictionary.set(
+                    "normal_b",
+                    $crate::rapier_wrapper::convert::vector_to_godot(result.normal2),
+                );
+                dictionary
+            }
+
+            #[func]
+            #[allow(clippy::too_many_arguments)]
+            /// Sweep [param shape] along a ballistic path through [param space], starting at
+            /// [param start_transform] with [param velocity] and accelerated by [param gravity],
+            /// for up to [param max_time] seconds. Bodies in [param mask] are hit; areas are
+            /// not.
+            /// On each hit the velocity is reflected about the surface normal, keeping
+            /// [param bounce] of its normal component, until [param max_bounces] bounces are
+            /// used and the next hit ends the path. A shape that starts inside a body and moves
+            /// further into it hits that body at time zero.
+            /// Returns a dictionary with [code]points[/code], the shape origins along the path,
+            /// [code]hits[/code], an array of dictionaries with [code]rid[/code],
+            /// [code]collider_id[/code], [code]shape[/code], [code]point[/code],
+            /// [code]normal[/code] and [code]time[/code], [code]time[/code], the seconds
+            /// simulated, and [code]truncated[/code], true when the path was cut off at about a
+            /// minute of flight before reaching [param max_time]. A warning is also printed
+            /// then.
+            pub fn space_cast_trajectory(
+                space: Rid,
+                shape: Rid,
+                start_transform: Transform,
+                velocity: Vector,
+                gravity: Vector,
+                max_time: real,
+                max_bounces: i32,
+                bounce: real,
+                mask: u32,
+            ) -> VarDictionary {
+                use $crate::bodies::rapier_collision_object_base::RapierCollisionObjectBase;
+                use $crate::rapier_wrapper::convert::vector_to_godot;
+                use $crate::rapier_wrapper::convert::vector_to_rapier;
+                use $crate::rapier_wrapper::prelude::*;
+                use $crate::shapes::rapier_shape::IRapierShape;
+                let physics_data = physics_data();
+                let mut result = Default::default();
+                if let Some(shape) = physics_data.shapes.get(&shape)
+                    && let Some(space) = physics_data.spaces.get(&space)
+                {
+                    let query_excluded_info = QueryExcludedInfo {
+                        query_collision_layer_mask: mask,
+                        ..Default::default()
+                    };
+                    result = physics_data.physics_engine.shape_cast_trajectory(
+                        space.get_state().get_id(),
+                        shape_info_from_body_shape(shape.get_base().get_id(), start_transform),
+                        vector_to_rapier(velocity),
+                        vector_to_rapier(gravity),
+                        max_time.max(0.0),
+                        max_bounces.max(0) as usize,
+                        bounce,
+                        &query_excluded_info,
+                        &physics_data.collision_objects,
+                        &physics_data.ids,
+                        space,
+                    );
+                }
+                if result.truncated {
+                    godot_warn!(
+                        "Trajectory cast truncated after {} of {} seconds",
+                        result.time,
+                        max_time
+                    );
+                }
+                let mut points = PackedVectorArray::new();
+                for point in &result.pixel_points {
+                    points.push(vector_to_godot(*point));
+                }
+                let mut hits = Array::<VarDictionary>::new();
+                for hit in &result.hits {
+                    let (rid, shape) = RapierCollisionObjectBase::get_collider_user_data(
+                        &hit.user_data,
+                        &physics_data.ids,
+                    );
+                    let collider_id = physics_data
+                        .collision_objects
+                        .get(&rid)
+                        .map(|object| object.get_base().get_instance_id())
+                        .unwrap_or_default();
+                    let mut dictionary = VarDictionary::new();
+                    dictionary.set("rid", rid);
+                    dictionary.set("collider_id", collider_id as i64);
+                    dictionary.set("shape", shape as i64);
+                    dictionary.set("point", vector_to_godot(hit.pixel_position));
+                    dictionary.set("normal", vector_to_godot(hit.normal));
+                    dictionary.set("time", hit.time);
+                    hits.push(&dictionary);
+                }
+                let mut dictionary = VarDictionary::new();
+                dictionary.set("points", &points);
+                dictionary.set("hits", &hits);
+                dictionary.set("time", result.time);
+                dictionary.set("truncated", result.truncated);
+                dictionary
+            }
+
+            #[func]
+            /// Set an extra parameter for a shape. Bodies using the shape recreate their
+            /// colliders.
+            /// If [param param] is [member SHAPE_PARAM_CONVEX_DECOMPOSITION] (0), sets whether a
+            /// concave shape collides as its convex decomposition when attached to a dynamic
+            /// body, overriding the
+            /// [code]physics/rapier/shapes/convex_decomposition_on_dynamic_bodies[/code] project
+            /// setting. Pass [code]null[/code] to follow the project setting again.
+            /// If [param param] is [member SHAPE_PARAM_TRIMESH_FLAGS] (1), sets the parry
+            /// [code]TriMeshFlags[/code] bits a [ConcavePolygonShape3D] is built with, in the
+            /// order of the [code]physics/rapier/shapes/trimesh_flags[/code] project setting
+            /// which [code]null[/code] follows again. The trimesh is rebuilt with the new flags.
+            pub fn shape_set_extra_param(shape: Rid, param: i32, value: Variant) {
+                use $crate::shapes::rapier_shape::IRapierShape;
+                use $crate::shapes::rapier_shape_base::RapierShapeBase;
+                let physics_data = physics_data();
+                let Some(shape) = physics_data.shapes.get_mut(&shape) else {
+                    return;
+                };
+                let param = RapierShapeParam::from_i32(param);
+                shape.get_mut_base().set_extra_param(
+                    param,
+                    value,
+                    &mut physics_data.physics_engine,
+                );
+                #[cfg(feature = "dim3")]
+                if param == RapierShapeParam::TrimeshFlags
+                    && let $crate::shapes::rapier_shape::RapierShape::RapierConcavePolygonShape(shape) = shape
+                {
+                    let data = shape.get_data(&physics_data.physics_engine);
+                    shape.set_data(data, &mut physics_data.physics_engine);
+                }
+                let owners = shape.get_base().get_owners().clone();
+                let shape_id = shape.get_base().get_id();
+                RapierShapeBase::call_shape_changed(owners, shape_id, physics_data);
+            }
+
+            #[func]
+            /// Get an extra parameter for a shape.
+            /// If [param param] is [member SHAPE_PARAM_CONVEX_DECOMPOSITION] (0), gets whether a
+            /// concave shape collides as its convex decomposition when attached to a dynamic
+            /// body.
+            /// If [param param] is [member SHAPE_PARAM_TRIMESH_FLAGS] (1), gets the trimesh
+            /// flags bits the shape is built with.
+            pub fn shape_get_extra_param(shape: Rid, param: i32) -> Variant {
+                use $crate::shapes::rapier_shape::IRapierShape;
+                let physics_data = physics_data();
+                if let Some(shape) = physics_data.shapes.get(&shape) {
+                    return shape
+                        .get_base()
+                        .get_extra_param(RapierShapeParam::from_i32(param), &physics_data.physics_engine);
+                }
+                Variant::nil()
+            }
+
+            #[func]
+            /// Round the edges and corners of a rectangle, box, convex polygon, cylinder or custom
+            /// [param shape] with a border of [param radius], so bodies slide over seams and
+            /// edges smoothly. The border is taken from inside the shape, which keeps its size,
+            /// and [param radius] is clamped to what the shape can lose. A [param radius] of
+            /// [code]0[/code] makes the shape sharp again. Bodies using the shape recreate their
+            /// colliders.
+            pub fn shape_set_border_radius(shape: Rid, radius: real) {
+                let Some(mut physics_singleton) = try_rapier_physics_server() else {
+                    return;
+                };
+                physics_singleton
+                    .bind_mut()
+                    .implementation
+                    .shape_set_border_radius(shape, radius)
+            }
+
+            #[func]
+            /// Get the border radius set with [method shape_set_border_radius].
+            pub fn shape_get_border_radius(shape: Rid) -> real {
+                let Some(physics_singleton) = try_rapier_physics_server() else {
+                    return 0.0;
+                };
+                physics_singleton
+                    .bind()
+                    .implementation
+                    .shape_get_border_radius(shape)
+            }
+
+            #[cfg(feature = "dim2")]
+            #[func]
+            /// Create a heightmap shape, the 2D counterpart of [HeightMapShape3D]. Its data is a
+            /// dictionary with the [code]heights[/code], a packed float array of y
+            /// coordinates, and the horizontal [code]spacing[/code] between two consecutive
+            /// heights. The heightmap is centered on the shape origin.
+            pub fn heightmap_shape_create() -> Rid {
+                let Some(mut physics_singleton) = try_rapier_physics_server() else {
+                    return Rid::Invalid;
+                };
+                physics_singleton
+                    .bind_mut()
+                    .implementation
+                    .heightmap_shape_create()
+            }
+
+            #[cfg(feature = "dim3")]
+            #[func]
+            /// Replace the heights of a [code]width[/code] by [code]depth[/code] region of a
+            /// [HeightMapShape3D] starting at column [code]x[/code] and row [code]z[/code],
+            /// updating the bodies using it in place. A NaN height makes a hole of the cells
+            /// around it. Returns [code]false[/code] if the region does not fit in the heightmap.
+            pub fn heightmap_update_region(
+                shape: Rid,
+                x: i32,
+                z: i32,
+                width: i32,
+                depth: i32,
+                heights: PackedFloatArray,
+            ) -> bool {
+                let Some(mut physics_singleton) = try_rapier_physics_server() else {
+                    return false;
+                };
+                physics_singleton
+                    .bind_mut()
+                    .implementation
+                    .heightmap_update_region(shape, x, z, width, depth, heights.as_slice())
+            }
+
+            #[func]
+            /// Create a voxel shape. Its data is a dictionary with the [code]voxel_size[/code]
+            /// and the filled [code]cells[/code], a [PackedInt32Array] of consecutive integer
+            /// grid coordinates.
+            pub fn voxel_shape_create() -> Rid {
+                let Some(mut physics_singleton) = try_rapier_physics_server() else {
+                    return Rid::Invalid;
+                };
+                physics_singleton
+                    .bind_mut()
+                    .implementation
+                    .voxel_shape_create()
+            }
+
+            #[func]
+            /// Fill or clear the voxel at [param cell] of the voxel [param shape]. Bodies using
+            /// the shape are updated in place instead of having their colliders recreated, when
+            /// their collider holds the voxels as they are, and are woken up with their mass
+            /// updated. Returns whether the cell was filled before.
+            pub fn voxel_shape_set_voxel(shape: Rid, cell: Vectori, filled: bool) -> bool {
+                let Some(mut physics_singleton) = try_rapier_physics_server() else {
+                    return false;
+                };
+                physics_singleton
+                    .bind_mut()
+                    .implementation
+                    .voxel_shape_set_voxel(shape, cell, filled)
+            }
+
+            #[func]
+            /// Get the signed distance between [param shape_a] placed at [param xform_a] and
+            /// [param shape_b] placed at [param xform_b], however far apart they are.
+            /// Returns a dictionary with [code]distance[/code], negative when the shapes
+            /// overlap, the closest points [code]point_a[/code] and [code]point_b[/code], and
+            /// [code]normal[/code], pointing from [param shape_a] towards [param shape_b].
+            /// Returns an empty dictionary if either shape is invalid.
+            pub fn shape_distance(
+                shape_a: Rid,
+                xform_a: Transform,
+                shape_b: Rid,
+                xform_b: Transform,
+            ) -> VarDictionary {
+                use $crate::rapier_wrapper::prelude::*;
+                use $crate::shapes::rapier_shape::IRapierShape;
+                let physics_data = physics_data();
+                let (Some(shape_a), Some(shape_b)) = (
+                    physics_data.shapes.get(&shape_a),
+                    physics_data.shapes.get(&shape_b),
+                ) else {
+                    return VarDictionary::new();
+                };
+                let result = physics_data.physics_engine.shapes_distance(
+                    shape_info_from_body_shape(shape_a.get_base().get_id(), xform_a),
+                    shape_info_from_body_shape(shape_b.get_base().get_id(), xform_b),
+                );
+                Self::distance_result_to_dictionary(&result)
+            }
+
+            #[func]
+            /// Get the signed distance between the closest shapes of [param body_a] and
+            /// [param body_b], which must be in the same space. Returns the same dictionary as
+            /// [method shape_distance], or an empty one if either body is invalid or has no
+            /// enabled shapes.
+            pub fn bodies_distance(body_a: Rid, body_b: Rid) -> VarDictionary {
+                let physics_data = physics_data();
+                if let Some(object_a) = physics_data.collision_objects.get(&body_a)
+                    && let Some(object_b) = physics_data.collision_objects.get(&body_b)
+                    && let Some(body_a) = object_a.get_body()
+                    && let Some(body_b) = object_b.get_body()
+                    && body_a.get_base().get_space_id() == body_b.get_base().get_space_id()
+                {
+                    let result = physics_data.physics_engine.bodies_distance(
+                        body_a.get_base().get_space_id(),
+                        body_a.get_base().get_body_handle(),
+                        body_b.get_base().get_body_handle(),
+                    );
+                    return Self::distance_result_to_dictionary(&result);
+                }
+                VarDictionary::new()
+            }
+
+            /// Result dictionary of [method shape_distance] and [method bodies_distance], empty
+            /// when no distance was measured.
+            fn distance_result_to_dictionary(
+                result: &$crate::rapier_wrapper::query::ContactResult,
+            ) -> VarDictionary {
+                use $crate::rapier_wrapper::convert::vector_to_godot;
+                let mut dictionary = VarDictionary::new();
+                if !result.collided {
+                    return dictionary;
+                }
+                dictionary.set("distance", result.pixel_distance);
+                dictionary.set("point_a", vector_to_godot(result.pixel_point1));
+                dictionary.set("point_b", vector_to_godot(result.pixel_point2));
+                dictionary.set("normal", vector_to_godot(result.normal1));
+                dictionary
+            }
+
+            #[func]
+            #[allow(clippy::too_many_arguments)]
+            /// Push every rigid body in [param space] with a shape within [param radius] of
+            /// [param center] away from it. Each body gets one impulse at the point of its
+            /// shapes closest to [param center], of strength [param impulse] scaled by
+            /// [code](1 - distance / radius)[/code] raised to [param falloff]; a
+            /// [param falloff] of [code]0[/code] applies the full impulse everywhere. Only
+            /// shapes in [param mask] are affected or block the blast. With
+            /// [param occlusion], a body is skipped when another body lies between it and
+            /// [param center].
+            /// Returns an array of dictionaries with [code]rid[/code], [code]point[/code] and
+            /// [code]impulse[/code], the world-space impulse applied, nearest bodies first.
+            pub fn space_apply_explosion(
+                space: Rid,
+                center: Vector,
+                radius: real,
+                impulse: real,
+                falloff: real,
+                mask: u32,
+                occlusion: bool,
+            ) -> Array<VarDictionary> {
+                use $crate::bodies::rapier_collision_object_base::RapierCollisionObjectBase;
+                use $crate::rapier_wrapper::convert::vector_to_godot;
+                use $crate::rapier_wrapper::convert::vector_to_rapier;
+                use $crate::rapier_wrapper::prelude::*;
+                let mut results = Array::new();
+                let physics_data = physics_data();
+                let Some(space) = physics_data.spaces.get(&space) else {
+                    return results;
+                };
+                if radius <= 0.0 {
+                    return results;
+                }
+                let query_excluded_info = QueryExcludedInfo {
+                    query_collision_layer_mask: mask,
+                    ..Default::default()
+                };
+                let hits = physics_data.physics_engine.explosion_query(
+                    space.get_state().get_id(),
+                    vector_to_rapier(center),
+                    radius,
+                    occlusion,
+                    &query_excluded_info,
+                    &physics_data.collision_objects,
+                    &physics_data.ids,
+                    space,
+                );
+                for hit in hits {
+                    let (rid, _) = RapierCollisionObjectBase::get_collider_user_data(
+                        &hit.user_data,
+                        &physics_data.ids,
+                    );
+                    let Some(body) = physics_data.collision_objects.get_mut(&rid) else {
+                        continue;
+                    };
+                    let Some(body) = body.get_mut_body() else {
+                        continue;
+                    };
+                    if body.get_base().get_mode().ord() < BodyMode::RIGID.ord() {
+                        continue;
+                    }
+                    let point = vector_to_godot(hit.pixel_point);
+                    // Inside a shape there is no surface direction to push along, so push
+                    // from the center through the body's center of mass instead.
+                    let mut direction = point - center;
+                    if direction.length_squared() <= real::EPSILON {
+                        direction =
+                            body.get_base().get_transform() * body.get_center_of_mass() - center;
+                    }
+                    if direction.length_squared() <= real::EPSILON {
+                        direction = Vector::UP;
+                    }
+                    let strength = impulse
+                        * (1.0 - hit.pixel_distance / radius)
+                            .clamp(0.0, 1.0)
+                            .powf(falloff.max(0.0));
+                    let applied_impulse = direction.normalized() * strength;
+                    body.force_mass_update(
+                        &mut physics_data.spaces,
+                        &mut physics_data.physics_engine,
+                        &physics_data.ids,
+                    );
+                    body.apply_impulse(
+                        applied_impulse,
+                        point - body.get_base().get_transform().origin,
+                        &mut physics_data.physics_engine,
+                    );
+                    let mut dictionary = VarDictionary::new();
+                    dictionary.set("rid", rid);
+                    dictionary.set("point", point);
+                    dictionary.set("impulse", applied_impulse);
+                    results.push(&dictionary);
+                }
+                results
+            }
+
             #[func]
             /// Get the world-space linear impulse a joint applied to hold its constraint on the
             /// last step, including its limit and motor contributions. Use it for breakable
@@ -286,6 +1083,67 @@ macro_rules! make_rapier_server_godot_impl {
                 ANGLE_ZERO
             }
 
+            #[func]
+            /// Break [param joint] once the impulse it applies in one step goes over
+            /// [param max_impulse], or its angular impulse goes over
+            /// [param max_angular_impulse]. Pass [code]INF[/code] to leave either unlimited.
+            /// A broken joint is disabled, not freed, and is reported once by
+            /// [method space_get_broken_joints]. Setting the threshold again re-arms it, as does
+            /// setting the joint up again.
+            /// Multibody and coupling joints report no reaction impulse and never break.
+            pub fn joint_set_break_threshold(
+                joint: Rid,
+                max_impulse: real,
+                max_angular_impulse: real,
+            ) {
+                let physics_data = physics_data();
+                if let Some(joint) = physics_data.joints.get_mut(&joint) {
+                    joint.get_mut_base().set_break_threshold(
+                        max_impulse.max(0.0),
+                        max_angular_impulse.max(0.0),
+                        &mut physics_data.physics_engine,
+                    );
+                    $crate::spaces::rapier_space::RapierSpace::update_breakable_joint(
+                        joint.get_base().get_id(),
+                        Some(joint.get_base()),
+                        &mut physics_data.spaces,
+                        &physics_data.ids,
+                    );
+                }
+            }
+
+            #[func]
+            /// Check whether [param joint] went over its break threshold, see
+            /// [method joint_set_break_threshold].
+            pub fn joint_is_broken(joint: Rid) -> bool {
+                let physics_data = physics_data();
+                if let Some(joint) = physics_data.joints.get(&joint) {
+                    return joint.get_base().is_broken();
+                }
+                false
+            }
+
+            #[func]
+            /// Get the joints of [param space] that broke since the last call, in the order they
+            /// broke. Joints that broke on the same step are listed in the order they were
+            /// created. See [method joint_set_break_threshold].
+            pub fn space_get_broken_joints(space: Rid) -> Array<Rid> {
+                let physics_data = physics_data();
+                let mut joints = Array::new();
+                if let Some(space) = physics_data.spaces.get_mut(&space) {
+                    for joint in space.get_mut_state().take_broken_joint_list() {
+                        let rid = $crate::servers::rapier_physics_singleton::get_id_rid(
+                            joint,
+                            &physics_data.ids,
+                        );
+                        if physics_data.joints.contains_key(&rid) {
+                            joints.push(rid);
+                        }
+                    }
+                }
+                joints
+            }
+
             #[func]
             /// Set an extra parameter for a joint.
             /// If [param param] is [member JOINT_TYPE] (0), sets if multibody or not.
@@ -362,6 +1220,108 @@ macro_rules! make_rapier_server_godot_impl {
                 }
             }
 
+            #[func]
+            /// Solve inverse kinematics for several links of the same multibody at once, such as
+            /// the feet and hands of a creature. Each entry of [param targets] is a dictionary
+            /// with [code]joint[/code], the multibody joint of the link to move,
+            /// [code]transform[/code], its target, and optionally [code]weight[/code]
+            /// (default [code]1.0[/code]), its importance relative to the other targets, and
+            /// [code]constrained_axes[/code] (default: every axis), the axes to solve for as in
+            /// [method joint_set_ik_options]. Damping, iterations and tolerances come from the
+            /// IK options of the first target's joint. The root body of the multibody is never
+            /// moved, only the joints leading to the targets. Impulse joints are rejected.
+            /// Returns the solved coordinates of every link, one packed float array per body in
+            /// the order of [method joint_get_multibody_bodies], each laid out as
+            /// [method joint_get_multibody_positions]. The solution is only applied when
+            /// [param apply] is [code]true[/code]. Returns an empty array if solving fails.
+            pub fn joint_solve_inverse_kinematics_targets(
+                targets: Array<VarDictionary>,
+                apply: bool,
+            ) -> Array<PackedFloatArray> {
+                use rapier::dynamics::JointAxesMask;
+                use $crate::rapier_wrapper::convert::vector_to_rapier;
+                use $crate::rapier_wrapper::joint::InverseKinematicsTarget;
+                let physics_data = physics_data();
+                let mut results = Array::new();
+                let mut space_handle = None;
+                let mut options = None;
+                let mut ik_targets = Vec::with_capacity(targets.len());
+                for target in targets.iter_shared() {
+                    let Some(joint) = target
+                        .get("joint")
+                        .and_then(|joint| joint.try_to::<Rid>().ok())
+                        .and_then(|joint| physics_data.joints.get(&joint))
+                    else {
+                        godot_error!("IK target has no valid joint");
+                        return results;
+                    };
+                    if joint.get_base().get_joint_type() == RapierJointType::Impulse {
+                        godot_error!("IK target joint must be a multibody joint");
+                        return results;
+                    }
+                    let Some(target_transform) = target
+                        .get("transform")
+                        .and_then(|transform| transform.try_to::<Transform>().ok())
+                    else {
+                        godot_error!("IK target has no valid transform");
+                        return results;
+                    };
+                    let joint_space = joint.get_base().get_space_id();
+                    if space_handle.is_some_and(|space_handle| space_handle != joint_space) {
+                        godot_error!("IK targets must all be in the same space");
+                        return results;
+                    }
+                    space_handle = Some(joint_space);
+                    options.get_or_insert(joint.get_base().custom_ik_options);
+                    let weight = target
+                        .get("weight")
+                        .and_then(|weight| weight.try_to::<real>().ok())
+                        .unwrap_or(1.0);
+                    let constrained_axes = target
+                        .get("constrained_axes")
+                        .and_then(|axes| axes.try_to::<i32>().ok())
+                        .map_or(JointAxesMask::all(), |axes| {
+                            JointAxesMask::from_bits_truncate(axes.max(0) as u8)
+                        });
+                    ik_targets.push(InverseKinematicsTarget {
+                        joint_handle: joint.get_base().get_handle(),
+                        target_transform: rapier::prelude::Pose::from_parts(
+                            vector_to_rapier(target_transform.origin),
+                            transform_rotation_rapier(&target_transform),
+                        ),
+                        weight,
+                        constrained_axes,
+                    });
+                }
+                let (Some(space_handle), Some(options)) = (space_handle, options) else {
+                    return results;
+                };
+                let solved = physics_data.physics_engine.multibody_solve_ik_targets(
+                    space_handle,
+                    &ik_targets,
+                    options,
+                    apply,
+                );
+                for link_positions in solved {
+                    let mut positions = PackedFloatArray::new();
+                    for position in link_positions {
+                        positions.push(position);
+                    }
+                    results.push(&positions);
+                }
+                if apply && let Some(target) = ik_targets.first() {
+                    for body in physics_data
+                        .physics_engine
+                        .get_multibody_rigidbodies(space_handle, target.joint_handle)
+                    {
+                        physics_data
+                            .physics_engine
+                            .body_wake_up(space_handle, body, true);
+                    }
+                }
+                results
+            }
+
             #[func]
             /// Set custom IK options for a specific joint.
             /// This overrides the default Rapier IK parameters.
@@ -404,6 +1364,198 @@ macro_rules! make_rapier_server_godot_impl {
                 }
             }
 
+            #[func]
+            /// Get the generalized coordinates of the link [param joint] drives in its multibody,
+            /// one per free axis of the joint: unlocked linear axes first, then unlocked angular
+            /// axes. Angles are in radians. Only joints with at most one free angular axis are
+            /// supported: fixed, slider, hinge and generic 6DOF joints locking at least two
+            /// angular axes. Pin and cone twist joints, and impulse joints, return an empty
+            /// array.
+            pub fn joint_get_multibody_positions(joint: Rid) -> PackedFloatArray {
+                let physics_data = physics_data();
+                let mut positions = PackedFloatArray::new();
+                if let Some(joint) = physics_data.joints.get(&joint)
+                    && joint.get_base().get_joint_type() != RapierJointType::Impulse
+                {
+                    for position in physics_data.physics_engine.multibody_get_link_positions(
+                        joint.get_base().get_space_id(),
+                        joint.get_base().get_handle(),
+                    ) {
+                        positions.push(position);
+                    }
+                }
+                positions
+            }
+
+            #[func]
+            /// Move the link [param joint] drives to [param positions], in the order of
+            /// [method joint_get_multibody_positions], and update the bodies of the multibody
+            /// to match. Missing entries keep their current value. Joints with more than one
+            /// free angular axis are rejected.
+            pub fn joint_set_multibody_positions(joint: Rid, positions: PackedFloatArray) {
+                let physics_data = physics_data();
+                if let Some(joint) = physics_data.joints.get(&joint)
+                    && joint.get_base().get_joint_type() != RapierJointType::Impulse
+                {
+                    let space_handle = joint.get_base().get_space_id();
+                    let joint_handle = joint.get_base().get_handle();
+                    physics_data.physics_engine.multibody_set_link_positions(
+                        space_handle,
+                        joint_handle,
+                        positions.as_slice(),
+                    );
+                    for body in physics_data
+                        .physics_engine
+                        .get_multibody_rigidbodies(space_handle, joint_handle)
+                    {
+                        physics_data
+                            .physics_engine
+                            .body_wake_up(space_handle, body, true);
+                    }
+                }
+            }
+
+            #[func]
+            /// Get the generalized velocities of the link [param joint] drives, in the order of
+            /// [method joint_get_multibody_positions].
+            pub fn joint_get_multibody_velocities(joint: Rid) -> PackedFloatArray {
+                let physics_data = physics_data();
+                let mut velocities = PackedFloatArray::new();
+                if let Some(joint) = physics_data.joints.get(&joint)
+                    && joint.get_base().get_joint_type() != RapierJointType::Impulse
+                {
+                    for velocity in physics_data.physics_engine.multibody_get_link_velocities(
+                        joint.get_base().get_space_id(),
+                        joint.get_base().get_handle(),
+                    ) {
+                        velocities.push(velocity);
+                    }
+                }
+                velocities
+            }
+
+            #[func]
+            /// Set the generalized velocities of the link [param joint] drives, in the order of
+            /// [method joint_get_multibody_positions]. Missing entries keep their current value.
+            pub fn joint_set_multibody_velocities(joint: Rid, velocities: PackedFloatArray) {
+                let physics_data = physics_data();
+                if let Some(joint) = physics_data.joints.get(&joint)
+                    && joint.get_base().get_joint_type() != RapierJointType::Impulse
+                {
+                    let space_handle = joint.get_base().get_space_id();
+                    let joint_handle = joint.get_base().get_handle();
+                    physics_data.physics_engine.multibody_set_link_velocities(
+                        space_handle,
+                        joint_handle,
+                        velocities.as_slice(),
+                    );
+                    for body in physics_data
+                        .physics_engine
+                        .get_multibody_rigidbodies(space_handle, joint_handle)
+                    {
+                        physics_data
+                            .physics_engine
+                            .body_wake_up(space_handle, body, true);
+                    }
+                }
+            }
+
+            #[func]
+            /// Apply generalized [param forces] (torques on angular axes) to the link
+            /// [param joint] drives for one physics step, in the order of
+            /// [method joint_get_multibody_positions]. Call it every step for a continuous
+            /// force. The response of each axis uses its own inverse inertia, so it is exact for
+            /// a single link and approximate when several links of the chain move together.
+            pub fn joint_apply_multibody_forces(joint: Rid, forces: PackedFloatArray) {
+                let physics_data = physics_data();
+                if let Some(joint) = physics_data.joints.get(&joint)
+                    && joint.get_base().get_joint_type() != RapierJointType::Impulse
+                {
+                    let space_handle = joint.get_base().get_space_id();
+                    let joint_handle = joint.get_base().get_handle();
+                    let step = $crate::spaces::rapier_space::RapierSpace::get_last_step();
+                    let impulses: Vec<real> =
+                        forces.as_slice().iter().map(|force| force * step).collect();
+                    physics_data.physics_engine.multibody_apply_link_impulses(
+                        space_handle,
+                        joint_handle,
+                        &impulses,
+                    );
+                    for body in physics_data
+                        .physics_engine
+                        .get_multibody_rigidbodies(space_handle, joint_handle)
+                    {
+                        physics_data
+                            .physics_engine
+                            .body_wake_up(space_handle, body, true);
+                    }
+                }
+            }
+
+            #[func]
+            /// Get the bodies of the multibody [param joint] belongs to, root first, then each
+            /// link after its parent.
+            pub fn joint_get_multibody_bodies(joint: Rid) -> Array<Rid> {
+                use $crate::bodies::rapier_collision_object_base::RapierCollisionObjectBase;
+                let physics_data = physics_data();
+                let mut bodies = Array::new();
+                if let Some(joint) = physics_data.joints.get(&joint)
+                    && joint.get_base().get_joint_type() != RapierJointType::Impulse
+                {
+                    let space_handle = joint.get_base().get_space_id();
+                    for body in physics_data
+                        .physics_engine
+                        .get_multibody_rigidbodies(space_handle, joint.get_base().get_handle())
+                    {
+                        let (rid, _) = RapierCollisionObjectBase::get_collider_user_data(
+                            &physics_data
+                                .physics_engine
+                                .body_get_user_data(space_handle, body),
+                            &physics_data.ids,
+                        );
+                        bodies.push(rid);
+                    }
+                }
+                bodies
+            }
+
+            #[func]
+            /// Enable or disable contacts between the links of the multibody [param joint]
+            /// belongs to. Enabled by default, in which case two links joined directly still
+            /// follow their joint's exclude-nodes-from-collision setting.
+            pub fn joint_set_multibody_self_collision(joint: Rid, enabled: bool) {
+                let physics_data = physics_data();
+                if let Some(joint) = physics_data.joints.get(&joint)
+                    && joint.get_base().get_joint_type() != RapierJointType::Impulse
+                {
+                    physics_data
+                        .physics_engine
+                        .multibody_set_self_contacts_enabled(
+                            joint.get_base().get_space_id(),
+                            joint.get_base().get_handle(),
+                            enabled,
+                        );
+                }
+            }
+
+            #[func]
+            /// Check whether links of the multibody [param joint] belongs to collide with each
+            /// other.
+            pub fn joint_is_multibody_self_collision_enabled(joint: Rid) -> bool {
+                let physics_data = physics_data();
+                if let Some(joint) = physics_data.joints.get(&joint)
+                    && joint.get_base().get_joint_type() != RapierJointType::Impulse
+                {
+                    return physics_data
+                        .physics_engine
+                        .multibody_is_self_contacts_enabled(
+                            joint.get_base().get_space_id(),
+                            joint.get_base().get_handle(),
+                        );
+                }
+                false
+            }
+
             #[func]
             pub fn joint_set_motor_position_options(
                 joint: Rid,
@@ -426,6 +1578,117 @@ macro_rules! make_rapier_server_godot_impl {
                 }
             }
 
+            #[cfg(feature = "dim3")]
+            #[func]
+            /// Drive all angular axes of a spherical, cone twist or generic 6DOF [param joint] with
+            /// position motors towards [param basis], the rotation of the joint frame of body B
+            /// relative to the joint frame of body A. Lets ragdolls follow an animated pose while
+            /// still reacting to hits.
+            ///
+            /// This replaces any angular velocity motor of the joint. Passing [code]0[/code] for
+            /// both [param stiffness] and [param damping] turns the angular motors off.
+            pub fn joint_set_target_rotation(
+                joint: Rid,
+                basis: Basis,
+                stiffness: real,
+                damping: real,
+            ) {
+                let physics_data = physics_data();
+                if let Some(joint) = physics_data.joints.get(&joint)
+                    && matches!(
+                        joint,
+                        RapierJoint::RapierSphericalJoint3D(_)
+                            | RapierJoint::RapierConeTwistJoint3D(_)
+                            | RapierJoint::RapierGeneric6DOFJoint3D(_)
+                    )
+                {
+                    physics_data.physics_engine.joint_set_target_rotation(
+                        joint.get_base().get_space_id(),
+                        joint.get_base().get_handle(),
+                        $crate::types::basis_to_rapier(basis),
+                        stiffness,
+                        damping,
+                    );
+                }
+            }
+
+            #[cfg(feature = "dim3")]
+            #[func]
+            /// Create a ragdoll in [param space] from the current pose of [param skeleton], with one
+            /// capsule body per bone and a cone twist joint linking each body to the body of its
+            /// closest included ancestor bone.
+            ///
+            /// Only bones named in [param bones] are included, or every bone when it is empty.
+            /// [param shape_hints] maps bone names to dictionaries that can override the
+            /// [code]radius[/code], [code]length[/code], [code]mass[/code],
+            /// [code]swing_span[/code] and [code]twist_span[/code] of that bone.
+            /// [param joint_type] is one of [member JOINT_TYPE_IMPULSE_JOINT],
+            /// [member JOINT_TYPE_MULTIBODY_JOINT] or [member JOINT_TYPE_MULTIBODY_KINEMATIC_JOINT].
+            ///
+            /// Returns a dictionary with [code]bodies[/code], [code]shapes[/code] and
+            /// [code]joints[/code] dictionaries mapping bone names to the created RIDs. Joints are
+            /// keyed by the name of their child bone. The caller owns the RIDs and frees them.
+            pub fn ragdoll_create(
+                space: Rid,
+                skeleton: Gd<godot::classes::Skeleton3D>,
+                bones: PackedStringArray,
+                shape_hints: VarDictionary,
+                joint_type: i32,
+            ) -> VarDictionary {
+                let Some(mut physics_singleton) = try_rapier_physics_server() else {
+                    return VarDictionary::new();
+                };
+                let joint_type = match joint_type {
+                    1 => RapierJointType::MultiBody,
+                    2 => RapierJointType::MultiBodyKinematic,
+                    _ => RapierJointType::Impulse,
+                };
+                physics_singleton.bind_mut().implementation.ragdoll_create(
+                    space,
+                    skeleton,
+                    bones,
+                    shape_hints,
+                    joint_type,
+                )
+            }
+
+            #[func]
+            #[allow(clippy::too_many_arguments)]
+            /// Create a rope in [param space] following [param points], made of capsule segments
+            /// of [param segment_radius] and [param segment_mass] linked by multibody pin joints,
+            /// so it sags and drapes over geometry.
+            ///
+            /// [param stiffness] drives every link back towards the bend it was created with, use
+            /// [code]0[/code] for a limp rope. The first point is pinned to [param attach_a] and the
+            /// last one to [param attach_b] when they are bodies, pass an invalid RID to leave an
+            /// end free.
+            ///
+            /// Returns a dictionary with [code]segments[/code], [code]shapes[/code] and
+            /// [code]joints[/code] arrays of the created RIDs, segments ordered from the first
+            /// point. The caller owns the RIDs and frees them.
+            pub fn space_create_rope(
+                space: Rid,
+                points: PackedVectorArray,
+                segment_radius: real,
+                segment_mass: real,
+                stiffness: real,
+                attach_a: Rid,
+                attach_b: Rid,
+            ) -> VarDictionary {
+                let Some(mut physics_singleton) = try_rapier_physics_server() else {
+                    return VarDictionary::new();
+                };
+                physics_singleton.bind_mut().implementation.space_create_rope(
+                    space,
+                    points,
+                    segment_radius,
+                    segment_mass,
+                    stiffness,
+                    attach_a,
+                    attach_b,
+                )
+            }
+
             #[cfg(feature = "serde-serialize")]
             pub fn fetch_state_internal<'a>(physics_object: Rid) -> Option<ObjectExportState<'a>> {
                 let physics_data = physics_data();
@@ -515,6 +1778,46 @@ macro_rules! make_rapier_server_godot_impl {
                 }
             }
 
+            #[func]
+            /// Get the current state of [param joint] for drawing it, or an empty dictionary if
+            /// it has no rapier joint behind it. Contains:
+            /// - [code]body_a[/code], [code]body_b[/code]: the joined bodies. For a multibody joint
+            /// [code]body_a[/code] is the parent link.
+            /// - [code]frame_a[/code], [code]frame_b[/code]: world space joint frames on each body.
+            /// - [code]axes[/code]: one dictionary per axis, linear axes first then angular ones,
+            /// with [code]position[/code] (offset or angle of frame B in frame A),
+            /// [code]locked[/code], [code]limited[/code], [code]limit_min[/code],
+            /// [code]limit_max[/code], [code]motor_enabled[/code],
+            /// [code]motor_target_position[/code] and [code]motor_target_velocity[/code].
+            pub fn joint_get_debug_info(joint: Rid) -> VarDictionary {
+                use $crate::bodies::rapier_collision_object_base::RapierCollisionObjectBase;
+                let physics_data = physics_data();
+                let Some(joint) = physics_data.joints.get(&joint) else {
+                    return VarDictionary::new();
+                };
+                let space_handle = joint.get_base().get_space_id();
+                let Some(info) = physics_data
+                    .physics_engine
+                    .joint_get_debug_info(space_handle, joint.get_base().get_handle())
+                else {
+                    return VarDictionary::new();
+                };
+                let body_rid = |body| {
+                    RapierCollisionObjectBase::get_collider_user_data(
+                        &physics_data
+                            .physics_engine
+                            .body_get_user_data(space_handle, body),
+                        &physics_data.ids,
+                    )
+                    .0
+                };
+                $crate::servers::rapier_physics_server_extra::joint_debug_info_to_dictionary(
+                    &info,
+                    body_rid(info.body_1),
+                    body_rid(info.body_2),
+                )
+            }
+
             #[func]
             /// Enable or disable [param joint] without destroying it.
             pub fn joint_set_enabled(joint: Rid, enabled: bool) {
@@ -553,7 +1856,10 @@ macro_rules! make_rapier_server_godot_impl {
                 if let Some(body_a) = physics_data.collision_objects.get(&body_a)
                     && let Some(body_b) = physics_data.collision_objects.get(&body_b)
                 {
-                    let id = physics_singleton.bind_mut().implementation.next_id();
+                    let id = physics_singleton
+                        .bind_mut()
+                        .implementation
+                        .joint_id(joint, &physics_data.joints);
                     let new_joint = RapierJoint::RapierRopeJoint(
                         $crate::joints::rapier_rope_joint::RapierRopeJoint::new(
                             id,
@@ -567,14 +1873,174 @@ macro_rules! make_rapier_server_godot_impl {
                             joint_type,
                         ),
                     );
-                    if let Some(mut prev_joint) = physics_data.joints.insert(joint, new_joint) {
-                        prev_joint
-                            .get_mut_base()
-                            .destroy_joint(&mut physics_data.physics_engine);
-                    }
+                    $crate::servers::rapier_physics_server_extra::replace_joint(joint, new_joint);
                 }
             }
 
+            #[func]
+            /// Turn [param joint] into a gear making [param body_b] spin [param ratio] times as
+            /// fast as [param body_a], around [param axis_b] and [param axis_a] respectively.
+            ///
+            /// Axes are in world space and are ignored in 2D. Use a negative [param ratio] to make
+            /// the bodies spin in opposite directions. Angles are coupled as well as velocities,
+            /// counted from the first step after the gear is made.
+            pub fn joint_make_gear(
+                joint: Rid,
+                axis_a: Vector,
+                axis_b: Vector,
+                ratio: real,
+                body_a: Rid,
+                body_b: Rid,
+            ) {
+                $crate::servers::rapier_physics_server_extra::make_coupling_joint(
+                    joint,
+                    $crate::joints::rapier_coupling_joint::RapierCouplingKind::Gear,
+                    axis_a,
+                    axis_b,
+                    ratio,
+                    body_a,
+                    body_b,
+                );
+            }
+
+            #[func]
+            /// Turn [param joint] into a rack and pinion: [param body_b] moves along
+            /// [param axis_b] at [param ratio] times the angular velocity of [param body_a] around
+            /// [param axis_a]. For a pinion of radius [code]r[/code] use [code]r[/code] as
+            /// [param ratio].
+            ///
+            /// Axes are in world space, [param axis_a] is ignored in 2D. Positions are coupled as
+            /// well as velocities, counted from the first step after the joint is made.
+            pub fn joint_make_rack_and_pinion(
+                joint: Rid,
+                axis_a: Vector,
+                axis_b: Vector,
+                ratio: real,
+                body_a: Rid,
+                body_b: Rid,
+            ) {
+                $crate::servers::rapier_physics_server_extra::make_coupling_joint(
+                    joint,
+                    $crate::joints::rapier_coupling_joint::RapierCouplingKind::RackAndPinion,
+                    axis_a,
+                    axis_b,
+                    ratio,
+                    body_a,
+                    body_b,
+                );
+            }
+
+            #[func]
+            /// Turn [param joint] into a pulley: [param body_b] moves along [param axis_b] at
+            /// [param ratio] times the speed of [param body_a] along [param axis_a].
+            ///
+            /// Axes are in world space. Positions are coupled as well as velocities, counted from
+            /// the first step after the pulley is made.
+            pub fn joint_make_pulley(
+                joint: Rid,
+                axis_a: Vector,
+                axis_b: Vector,
+                ratio: real,
+                body_a: Rid,
+                body_b: Rid,
+            ) {
+                $crate::servers::rapier_physics_server_extra::make_coupling_joint(
+                    joint,
+                    $crate::joints::rapier_coupling_joint::RapierCouplingKind::Pulley,
+                    axis_a,
+                    axis_b,
+                    ratio,
+                    body_a,
+                    body_b,
+                );
+            }
+
+            #[cfg(feature = "dim3")]
+            #[func]
+            /// Turn [param joint] into a damped spring pulling [param body_a] and [param body_b]
+            /// towards their current distance. Tune it with [method joint_set_spring_param].
+            ///
+            /// Anchors are in world space.
+            pub fn joint_make_spring(
+                joint: Rid,
+                anchor_a: Vector,
+                anchor_b: Vector,
+                body_a: Rid,
+                body_b: Rid,
+            ) {
+                let physics_data = physics_data();
+                let Some(mut physics_singleton) = try_rapier_physics_server() else {
+                    return;
+                };
+                let joint_type = if let Some(prev_joint) = physics_data.joints.get(&joint) {
+                    prev_joint.get_base().get_joint_type()
+                } else {
+                    $crate::joints::rapier_joint_base::RapierJointType::Impulse
+                };
+                if let Some(body_a) = physics_data.collision_objects.get(&body_a)
+                    && let Some(body_b) = physics_data.collision_objects.get(&body_b)
+                {
+                    let id = physics_singleton
+                        .bind_mut()
+                        .implementation
+                        .joint_id(joint, &physics_data.joints);
+                    let new_joint = RapierJoint::RapierSpringJoint3D(
+                        $crate::joints::rapier_spring_joint_3d::RapierSpringJoint3D::new(
+                            id,
+                            joint,
+                            anchor_a,
+                            anchor_b,
+                            body_a,
+                            body_b,
+                            &mut physics_data.physics_engine,
+                            joint_type,
+                        ),
+                    );
+                    $crate::servers::rapier_physics_server_extra::replace_joint(joint, new_joint);
+                }
+            }
+
+            #[cfg(feature = "dim3")]
+            #[func]
+            /// Set a parameter of a spring joint made with [method joint_make_spring].
+            /// If [param param] is [member SPRING_PARAM_REST_LENGTH] (0), sets the length the spring pulls towards.
+            /// If [param param] is [member SPRING_PARAM_STIFFNESS] (1), sets the spring stiffness.
+            /// If [param param] is [member SPRING_PARAM_DAMPING] (2), sets the spring damping.
+            /// If [param param] is [member SPRING_PARAM_MIN_LENGTH] (3), sets the shortest allowed length, a negative value removes it.
+            /// If [param param] is [member SPRING_PARAM_MAX_LENGTH] (4), sets the longest allowed length, a negative value removes it.
+            pub fn joint_set_spring_param(joint: Rid, param: i32, value: real) {
+                let physics_data = physics_data();
+                if let Some(RapierJoint::RapierSpringJoint3D(spring)) =
+                    physics_data.joints.get_mut(&joint)
+                {
+                    spring.set_param(
+                        $crate::servers::rapier_physics_server_extra::RapierSpringParam::from_i32(
+                            param,
+                        ),
+                        value,
+                        &mut physics_data.physics_engine,
+                    );
+                }
+            }
+
+            #[cfg(feature = "dim3")]
+            #[func]
+            /// Get a parameter of a spring joint made with [method joint_make_spring], see
+            /// [method joint_set_spring_param]. Unset lengths read as [code]-1[/code].
+            pub fn joint_get_spring_param(joint: Rid, param: i32) -> real {
+                let physics_data = physics_data();
+                if let Some(RapierJoint::RapierSpringJoint3D(spring)) =
+                    physics_data.joints.get(&joint)
+                {
+                    return spring.get_param(
+                        $crate::servers::rapier_physics_server_extra::RapierSpringParam::from_i32(
+                            param,
+                        ),
+                    );
+                }
+                0.0
+            }
+
             #[func]
             /// Turn [param joint] into a fixed joint locking all relative motion between
             /// [param body_a] and [param body_b].
@@ -593,7 +2059,10 @@ macro_rules! make_rapier_server_godot_impl {
                 if let Some(body_a) = physics_data.collision_objects.get(&body_a)
                     && let Some(body_b) = physics_data.collision_objects.get(&body_b)
                 {
-                    let id = physics_singleton.bind_mut().implementation.next_id();
+                    let id = physics_singleton
+                        .bind_mut()
+                        .implementation
+                        .joint_id(joint, &physics_data.joints);
                     let new_joint = RapierJoint::RapierFixedJoint(
                         $crate::joints::rapier_fixed_joint::RapierFixedJoint::new(
                             id,
@@ -606,11 +2075,7 @@ macro_rules! make_rapier_server_godot_impl {
                             joint_type,
                         ),
                     );
-                    if let Some(mut prev_joint) = physics_data.joints.insert(joint, new_joint) {
-                        prev_joint
-                            .get_mut_base()
-                            .destroy_joint(&mut physics_data.physics_engine);
-                    }
+                    $crate::servers::rapier_physics_server_extra::replace_joint(joint, new_joint);
                 }
             }
 
@@ -914,6 +2379,10 @@ macro_rules! make_rapier_server_godot_impl {
                     "physics_engine_shapes",
                     physics_data().physics_engine.shapes.len() as i64,
                 );
+                dictionary.set(
+                    "physics_engine_unique_shapes",
+                    physics_data().physics_engine.unique_shape_count() as i64,
+                );
                 dictionary.set(
                     "physics_worlds",
                     physics_data().physics_engine.physics_worlds.len() as i64,
@@ -11,7 +11,9 @@ use godot::global::rid_allocate_id;
 use godot::global::rid_from_int64;
 use godot::meta::conv::RawPtr;
 use godot::prelude::*;
+use rapier::prelude::ColliderHandle;
 
+use super::rapier_physics_singleton::PhysicsJoints;
 use super::rapier_physics_singleton::RapierId;
 use super::rapier_physics_singleton::get_id_rid;
 use super::rapier_physics_singleton::insert_id_rid;
@@ -40,7 +42,11 @@ use crate::shapes::rapier_circle_shape::RapierCircleShape;
 use crate::shapes::rapier_concave_polygon_shape::RapierConcavePolygonShape;
 use crate::shapes::rapier_convex_polygon_shape::RapierConvexPolygonShape;
 #[cfg(feature = "dim3")]
+use crate::shapes::rapier_custom_shape::RapierCustomShape;
+#[cfg(feature = "dim3")]
 use crate::shapes::rapier_cylinder_shape_3d::RapierCylinderShape3D;
+#[cfg(feature = "dim2")]
+use crate::shapes::rapier_heightmap_shape_2d::RapierHeightMapShape2D;
 #[cfg(feature = "dim3")]
 use crate::shapes::rapier_heightmap_shape_3d::RapierHeightMapShape3D;
 use crate::shapes::rapier_rectangle_shape::RapierRectangleShape;
@@ -48,7 +54,9 @@ use crate::shapes::rapier_rectangle_shape::RapierRectangleShape;
 use crate::shapes::rapier_segment_shape_2d::RapierSegmentShape2D;
 use crate::shapes::rapier_separation_ray_shape::RapierSeparationRayShape;
 use crate::shapes::rapier_shape::IRapierShape;
+use crate::shapes::rapier_shape::RapierShape;
 use crate::shapes::rapier_shape_base::RapierShapeBase;
+use crate::shapes::rapier_voxel_shape::RapierVoxelShape;
 use crate::shapes::rapier_world_boundary_shape::RapierWorldBoundaryShape;
 use crate::spaces::rapier_space::RapierSpace;
 use crate::types::*;
@@ -78,6 +86,15 @@ impl RapierPhysicsServerImpl {
         self.id
     }
 
+    /// Id for a joint made under `rid`: the id of the joint it replaces, which is the one
+    /// registered for the RID and the one the rapier joint is created with.
+    pub(super) fn joint_id(&mut self, rid: Rid, physics_joints: &PhysicsJoints) -> RapierId {
+        match physics_joints.get(&rid) {
+            Some(joint) => joint.get_base().get_id(),
+            None => self.next_id(),
+        }
+    }
+
     pub(super) fn default() -> Self {
         Self {
             id: RapierId::default(),
@@ -203,16 +220,179 @@ impl RapierPhysicsServerImpl {
         rid
     }
 
-    #[cfg(feature = "dim3")]
     pub(super) fn heightmap_shape_create(&mut self) -> Rid {
         let physics_data = physics_data();
         let rid = rid_from_int64(rid_allocate_id());
         let id = self.next_id();
+        #[cfg(feature = "dim2")]
+        RapierHeightMapShape2D::create(id, rid, &mut physics_data.shapes);
+        #[cfg(feature = "dim3")]
         RapierHeightMapShape3D::create(id, rid, &mut physics_data.shapes);
         insert_id_rid(id, rid, &mut physics_data.ids);
         rid
     }
 
+    #[cfg(feature = "dim3")]
+    pub(super) fn custom_shape_create(&mut self) -> Rid {
+        let physics_data = physics_data();
+        let rid = rid_from_int64(rid_allocate_id());
+        let id = self.next_id();
+        RapierCustomShape::create(id, rid, &mut physics_data.shapes);
+        insert_id_rid(id, rid, &mut physics_data.ids);
+        rid
+    }
+
+    pub(super) fn voxel_shape_create(&mut self) -> Rid {
+        let physics_data = physics_data();
+        let rid = rid_from_int64(rid_allocate_id());
+        let id = self.next_id();
+        RapierVoxelShape::create(id, rid, &mut physics_data.shapes);
+        insert_id_rid(id, rid, &mut physics_data.ids);
+        rid
+    }
+
+    /// Updates the shape and the collider of every body using it in place, so contacts and
+    /// sleeping state survive the edit. Owners with a collider that cannot be edited in place
+    /// have their colliders rebuilt instead. Either way their mass is updated and they are woken
+    /// up. Returns whether the cell was filled before the edit.
+    pub(super) fn voxel_shape_set_voxel(
+        &mut self,
+        shape: Rid,
+        cell: Vectori,
+        filled: bool,
+    ) -> bool {
+        let physics_data = physics_data();
+        let Some(RapierShape::RapierVoxelShape(voxel_shape)) = physics_data.shapes.get_mut(&shape)
+        else {
+            godot_error!("Shape is not a voxel shape");
+            return false;
+        };
+        let was_filled = voxel_shape.set_voxel(cell, filled, &mut physics_data.physics_engine);
+        let shape_id = voxel_shape.get_base().get_id();
+        let owners = voxel_shape.get_base().get_owners().clone();
+        for owner in owners.keys() {
+            let Some(owner) = physics_data
+                .collision_objects
+                .get_mut(&get_id_rid(*owner, &physics_data.ids))
+            else {
+                continue;
+            };
+            let mut edited = true;
+            let owner_base = owner.get_base();
+            for owner_shape in owner_base.state.shapes.iter() {
+                if owner_shape.id == shape_id
+                    && owner_shape.collider_handle != ColliderHandle::invalid()
+                {
+                    edited &= physics_data.physics_engine.collider_set_voxel(
+                        owner_base.get_space_id(),
+                        owner_shape.collider_handle,
+                        ivector_to_rapier(cell),
+                        filled,
+                    );
+                }
+            }
+            if edited {
+                owner.shapes_changed(
+                    &mut physics_data.physics_engine,
+                    &mut physics_data.spaces,
+                    &physics_data.ids,
+                );
+            } else {
+                owner.shape_changed(
+                    shape_id,
+                    &mut physics_data.physics_engine,
+                    &mut physics_data.spaces,
+                    &physics_data.ids,
+                );
+            }
+        }
+        was_filled
+    }
+
+    #[cfg(feature = "dim3")]
+    pub(super) fn heightmap_update_region(
+        &mut self,
+        shape: Rid,
+        x: i32,
+        z: i32,
+        width: i32,
+        depth: i32,
+        heights: &[real],
+    ) -> bool {
+        let physics_data = physics_data();
+        let Some(RapierShape::RapierHeightMapShape3D(heightmap_shape)) =
+            physics_data.shapes.get_mut(&shape)
+        else {
+            godot_error!("Shape is not a heightmap shape");
+            return false;
+        };
+        if !heightmap_shape.update_region(
+            x,
+            z,
+            width,
+            depth,
+            heights,
+            &mut physics_data.physics_engine,
+        ) {
+            godot_error!("Heightmap region is out of bounds");
+            return false;
+        }
+        let shape_id = heightmap_shape.get_base().get_id();
+        for owner in heightmap_shape.get_base().get_owners().keys() {
+            if let Some(owner) = physics_data
+                .collision_objects
+                .get(&get_id_rid(*owner, &physics_data.ids))
+            {
+                owner
+                    .get_base()
+                    .refresh_shape_colliders(shape_id, &mut physics_data.physics_engine);
+            }
+        }
+        true
+    }
+
+    /// Rebuilds the shape from its data with the new border, and recreates the colliders of its
+    /// owners.
+    pub(super) fn shape_set_border_radius(&mut self, shape: Rid, radius: real) {
+        let physics_data = physics_data();
+        let Some(shape) = physics_data.shapes.get_mut(&shape) else {
+            godot_error!("Invalid shape id");
+            return;
+        };
+        match shape {
+            RapierShape::RapierRectangleShape(_) | RapierShape::RapierConvexPolygonShape(_) => {}
+            #[cfg(feature = "dim3")]
+            RapierShape::RapierCylinderShape3D(_) | RapierShape::RapierCustomShape(_) => {}
+            _ => {
+                godot_error!("Shape does not support a border radius");
+                return;
+            }
+        }
+        let shape_id = shape.get_base().get_id();
+        let has_data = physics_data.physics_engine.get_shape(shape_id).is_some();
+        let data = shape.get_data(&physics_data.physics_engine);
+        physics_data
+            .physics_engine
+            .shape_set_border_radius(shape_id, radius);
+        // A shape without data yet gets its border once it is set.
+        if !has_data {
+            return;
+        }
+        shape.set_data(data, &mut physics_data.physics_engine);
+        let owners = shape.get_base().get_owners().clone();
+        RapierShapeBase::call_shape_changed(owners, shape_id, physics_data);
+    }
+
+    pub(super) fn shape_get_border_radius(&self, shape: Rid) -> real {
+        let physics_data = physics_data();
+        if let Some(shape) = physics_data.shapes.get(&shape) {
+            return physics_data
+                .physics_engine
+                .shape_get_border_radius(shape.get_base().get_id());
+        }
+        0.0
+    }
+
     pub(super) fn shape_set_data(&mut self, shape: Rid, data: Variant) {
         let physics_data = physics_data();
         let mut owners = None;
@@ -1707,11 +1887,17 @@ impl RapierPhysicsServerImpl {
     pub(super) fn joint_clear(&mut self, rid: Rid) {
         let physics_data = physics_data();
         if let Some(mut prev_joint) = physics_data.joints.remove(&rid) {
-            let id = self.next_id();
+            let id = self.joint_id(rid, &physics_data.joints);
             let mut joint = RapierEmptyJoint::new(id);
             joint
                 .get_mut_base()
                 .copy_settings_from(prev_joint.get_base(), &mut physics_data.physics_engine);
+            RapierSpace::update_breakable_joint(
+                joint.get_base().get_id(),
+                Some(joint.get_base()),
+                &mut physics_data.spaces,
+                &physics_data.ids,
+            );
             prev_joint
                 .get_mut_base()
                 .destroy_joint(&mut physics_data.physics_engine);
@@ -1782,7 +1968,7 @@ impl RapierPhysicsServerImpl {
         if let Some(body_a) = physics_data.collision_objects.get(&body_a)
             && let Some(body_b) = physics_data.collision_objects.get(&body_b)
         {
-            let id = self.next_id();
+            let id = self.joint_id(rid, &physics_data.joints);
             joint = RapierJoint::RapierSphericalJoint3D(RapierSphericalJoint3D::new(
                 id,
                 rid,
@@ -1801,13 +1987,19 @@ impl RapierPhysicsServerImpl {
                         prev_joint.get_base(),
                         &mut physics_data.physics_engine,
                     );
+                    RapierSpace::update_breakable_joint(
+                        joint.get_base().get_id(),
+                        Some(joint.get_base()),
+                        &mut physics_data.spaces,
+                        &physics_data.ids,
+                    );
                 }
                 prev_joint
                     .get_mut_base()
                     .destroy_joint(&mut physics_data.physics_engine);
             }
         } else {
-            let id = self.next_id();
+            let id = self.joint_id(rid, &physics_data.joints);
             joint = RapierJoint::RapierEmptyJoint(RapierEmptyJoint::new(id));
             physics_data.joints.insert(rid, joint);
         }
@@ -1889,7 +2081,7 @@ impl RapierPhysicsServerImpl {
         if let Some(body_a) = physics_data.collision_objects.get(&body_a)
             && let Some(body_b) = physics_data.collision_objects.get(&body_b)
         {
-            let id = self.next_id();
+            let id = self.joint_id(rid, &physics_data.joints);
             joint = RapierJoint::RapierRevoluteJoint(RapierRevoluteJoint::new(
                 id,
                 rid,
@@ -1910,13 +2102,19 @@ impl RapierPhysicsServerImpl {
                         prev_joint.get_base(),
                         &mut physics_data.physics_engine,
                     );
+                    RapierSpace::update_breakable_joint(
+                        joint.get_base().get_id(),
+                        Some(joint.get_base()),
+                        &mut physics_data.spaces,
+                        &physics_data.ids,
+                    );
                 }
                 prev_joint
                     .get_mut_base()
                     .destroy_joint(&mut physics_data.physics_engine);
             }
         } else {
-            let id = self.next_id();
+            let id = self.joint_id(rid, &physics_data.joints);
             joint = RapierJoint::RapierEmptyJoint(RapierEmptyJoint::new(id));
             physics_data.joints.insert(rid, joint);
         }
@@ -1972,7 +2170,7 @@ impl RapierPhysicsServerImpl {
             } else {
                 godot::prelude::Basis::IDENTITY
             };
-            let id = self.next_id();
+            let id = self.joint_id(rid, &physics_data.joints);
             joint = RapierJoint::RapierRevoluteJoint(RapierRevoluteJoint::new(
                 id,
                 rid,
@@ -1993,13 +2191,19 @@ impl RapierPhysicsServerImpl {
                         prev_joint.get_base(),
                         &mut physics_data.physics_engine,
                     );
+                    RapierSpace::update_breakable_joint(
+                        joint.get_base().get_id(),
+                        Some(joint.get_base()),
+                        &mut physics_data.spaces,
+                        &physics_data.ids,
+                    );
                 }
                 prev_joint
                     .get_mut_base()
                     .destroy_joint(&mut physics_data.physics_engine);
             }
         } else {
-            let id = self.next_id();
+            let id = self.joint_id(rid, &physics_data.joints);
             joint = RapierJoint::RapierEmptyJoint(RapierEmptyJoint::new(id));
             physics_data.joints.insert(rid, joint);
         }
@@ -2059,7 +2263,7 @@ impl RapierPhysicsServerImpl {
         if let Some(body_a) = physics_data.collision_objects.get(&body_a)
             && let Some(body_b) = physics_data.collision_objects.get(&body_b)
         {
-            let id = self.next_id();
+            let id = self.joint_id(rid, &physics_data.joints);
             joint = RapierJoint::RapierSliderJoint3D(RapierSliderJoint3D::new(
                 id,
                 rid,
@@ -2080,13 +2284,19 @@ impl RapierPhysicsServerImpl {
                         prev_joint.get_base(),
                         &mut physics_data.physics_engine,
                     );
+                    RapierSpace::update_breakable_joint(
+                        joint.get_base().get_id(),
+                        Some(joint.get_base()),
+                        &mut physics_data.spaces,
+                        &physics_data.ids,
+                    );
                 }
                 prev_joint
                     .get_mut_base()
                     .destroy_joint(&mut physics_data.physics_engine);
             }
         } else {
-            let id = self.next_id();
+            let id = self.joint_id(rid, &physics_data.joints);
             joint = RapierJoint::RapierEmptyJoint(RapierEmptyJoint::new(id));
             physics_data.joints.insert(rid, joint);
         }
@@ -2138,7 +2348,7 @@ impl RapierPhysicsServerImpl {
         if let Some(body_a) = physics_data.collision_objects.get(&body_a)
             && let Some(body_b) = physics_data.collision_objects.get(&body_b)
         {
-            let id = self.next_id();
+            let id = self.joint_id(rid, &physics_data.joints);
             joint = RapierJoint::RapierConeTwistJoint3D(RapierConeTwistJoint3D::new(
                 id,
                 rid,
@@ -2159,13 +2369,19 @@ impl RapierPhysicsServerImpl {
                         prev_joint.get_base(),
                         &mut physics_data.physics_engine,
                     );
+                    RapierSpace::update_breakable_joint(
+                        joint.get_base().get_id(),
+                        Some(joint.get_base()),
+                        &mut physics_data.spaces,
+                        &physics_data.ids,
+                    );
                 }
                 prev_joint
                     .get_mut_base()
                     .destroy_joint(&mut physics_data.physics_engine);
             }
         } else {
-            let id = self.next_id();
+            let id = self.joint_id(rid, &physics_data.joints);
             joint = RapierJoint::RapierEmptyJoint(RapierEmptyJoint::new(id));
             physics_data.joints.insert(rid, joint);
         }
@@ -2219,7 +2435,7 @@ impl RapierPhysicsServerImpl {
         if let Some(body_a) = physics_data.collision_objects.get(&body_a)
             && let Some(body_b) = physics_data.collision_objects.get(&body_b)
         {
-            let id = self.next_id();
+            let id = self.joint_id(rid, &physics_data.joints);
             joint = RapierJoint::RapierGeneric6DOFJoint3D(RapierGeneric6DOFJoint3D::new(
                 id,
                 rid,
@@ -2240,13 +2456,19 @@ impl RapierPhysicsServerImpl {
                         prev_joint.get_base(),
                         &mut physics_data.physics_engine,
                     );
+                    RapierSpace::update_breakable_joint(
+                        joint.get_base().get_id(),
+                        Some(joint.get_base()),
+                        &mut physics_data.spaces,
+                        &physics_data.ids,
+                    );
                 }
                 prev_joint
                     .get_mut_base()
                     .destroy_joint(&mut physics_data.physics_engine);
             }
         } else {
-            let id = self.next_id();
+            let id = self.joint_id(rid, &physics_data.joints);
             joint = RapierJoint::RapierEmptyJoint(RapierEmptyJoint::new(id));
             physics_data.joints.insert(rid, joint);
         }
@@ -2330,7 +2552,7 @@ impl RapierPhysicsServerImpl {
         if let Some(body_a) = physics_data.collision_objects.get(&body_a)
             && let Some(body_b) = physics_data.collision_objects.get(&body_b)
         {
-            let id = self.next_id();
+            let id = self.joint_id(rid, &physics_data.joints);
             joint = RapierJoint::RapierRevoluteJoint(RapierRevoluteJoint::new(
                 id,
                 rid,
@@ -2349,13 +2571,19 @@ impl RapierPhysicsServerImpl {
                         prev_joint.get_base(),
                         &mut physics_data.physics_engine,
                     );
+                    RapierSpace::update_breakable_joint(
+                        joint.get_base().get_id(),
+                        Some(joint.get_base()),
+                        &mut physics_data.spaces,
+                        &physics_data.ids,
+                    );
                 }
                 prev_joint
                     .get_mut_base()
                     .destroy_joint(&mut physics_data.physics_engine);
             }
         } else {
-            let id = self.next_id();
+            let id = self.joint_id(rid, &physics_data.joints);
             joint = RapierJoint::RapierEmptyJoint(RapierEmptyJoint::new(id));
             physics_data.joints.insert(rid, joint);
         }
@@ -2381,7 +2609,7 @@ impl RapierPhysicsServerImpl {
         if let Some(body_a) = physics_data.collision_objects.get(&body_a)
             && let Some(body_b) = physics_data.collision_objects.get(&body_b)
         {
-            let id = self.next_id();
+            let id = self.joint_id(rid, &physics_data.joints);
             joint = RapierJoint::RapierGrooveJoint2D(RapierGrooveJoint2D::new(
                 id,
                 rid,
@@ -2401,13 +2629,19 @@ impl RapierPhysicsServerImpl {
                         prev_joint.get_base(),
                         &mut physics_data.physics_engine,
                     );
+                    RapierSpace::update_breakable_joint(
+                        joint.get_base().get_id(),
+                        Some(joint.get_base()),
+                        &mut physics_data.spaces,
+                        &physics_data.ids,
+                    );
                 }
                 prev_joint
                     .get_mut_base()
                     .destroy_joint(&mut physics_data.physics_engine);
             }
         } else {
-            let id = self.next_id();
+            let id = self.joint_id(rid, &physics_data.joints);
             joint = RapierJoint::RapierEmptyJoint(RapierEmptyJoint::new(id));
             physics_data.joints.insert(rid, joint);
         }
@@ -2432,7 +2666,7 @@ impl RapierPhysicsServerImpl {
         if let Some(body_a) = physics_data.collision_objects.get(&body_a)
             && let Some(body_b) = physics_data.collision_objects.get(&body_b)
         {
-            let id = self.next_id();
+            let id = self.joint_id(rid, &physics_data.joints);
             joint = RapierJoint::RapierDampedSpringJoint2D(RapierDampedSpringJoint2D::new(
                 id,
                 rid,
@@ -2451,13 +2685,19 @@ impl RapierPhysicsServerImpl {
                         prev_joint.get_base(),
                         &mut physics_data.physics_engine,
                     );
+                    RapierSpace::update_breakable_joint(
+                        joint.get_base().get_id(),
+                        Some(joint.get_base()),
+                        &mut physics_data.spaces,
+                        &physics_data.ids,
+                    );
                 }
                 prev_joint
                     .get_mut_base()
                     .destroy_joint(&mut physics_data.physics_engine);
             }
         } else {
-            let id = self.next_id();
+            let id = self.joint_id(rid, &physics_data.joints);
             joint = RapierJoint::RapierEmptyJoint(RapierEmptyJoint::new(id));
             physics_data.joints.insert(rid, joint);
         }
@@ -2552,6 +2792,315 @@ impl RapierPhysicsServerImpl {
         }
     }
 
+    /// Builds a ragdoll for the current pose of `skeleton`: one capsule body per bone and a cone
+    /// twist joint to the body of the closest included ancestor bone. An empty `bones` filter
+    /// includes every bone. `shape_hints` maps bone names to dictionaries that can override the
+    /// `radius`, `length`, `mass`, `swing_span` and `twist_span` of that bone.
+    #[cfg(feature = "dim3")]
+    pub(super) fn ragdoll_create(
+        &mut self,
+        space: Rid,
+        skeleton: Gd<godot::classes::Skeleton3D>,
+        bones: PackedStringArray,
+        shape_hints: VarDictionary,
+        joint_type: RapierJointType,
+    ) -> VarDictionary {
+        let mut bodies = VarDictionary::new();
+        let mut shapes = VarDictionary::new();
+        let mut joints = VarDictionary::new();
+        let skeleton_transform = skeleton.get_global_transform();
+        let bone_count = skeleton.get_bone_count();
+        // Body rid and transform per bone index, for bones that got a body.
+        let mut bone_bodies: Vec<Option<(Rid, Transform3D)>> = vec![None; bone_count as usize];
+        let mut bone_lengths = vec![0.0; bone_count as usize];
+        for bone in 0..bone_count {
+            let bone_name = skeleton.get_bone_name(bone);
+            if !bones.is_empty() && !bones.contains(&bone_name) {
+                continue;
+            }
+            let hint: Option<VarDictionary> = shape_hints
+                .get(&bone_name)
+                .and_then(|hint| hint.try_to().ok());
+            let bone_transform = skeleton_transform * skeleton.get_bone_global_pose(bone);
+            let parent = skeleton.get_bone_parent(bone);
+            let child = skeleton.get_bone_children(bone).as_slice().first().copied();
+            let (direction, default_length) = match child {
+                Some(child) => {
+                    let child_origin =
+                        (skeleton_transform * skeleton.get_bone_global_pose(child)).origin;
+                    let offset = child_origin - bone_transform.origin;
+                    (offset.normalized(), offset.length())
+                }
+                None => {
+                    let parent_length = if parent >= 0 {
+                        bone_lengths[parent as usize]
+                    } else {
+                        0.0
+                    };
+                    (
+                        bone_transform.basis.col_b().normalized(),
+                        if parent_length > 0.0 {
+                            parent_length * 0.5
+                        } else {
+                            0.1
+                        },
+                    )
+                }
+            };
+            if !direction.is_finite() || direction == Vector3::ZERO {
+                continue;
+            }
+            let length = ragdoll_hint(&hint, "length", default_length);
+            let radius = ragdoll_hint(&hint, "radius", length * 0.2);
+            bone_lengths[bone as usize] = length;
+            // Capsules are built along Y, so align Y with the bone and keep the bone's X as close
+            // as possible.
+            let mut x_axis = bone_transform.basis.col_a();
+            x_axis -= direction * x_axis.dot(direction);
+            if x_axis.length_squared() < 1e-6 {
+                x_axis = if direction.x.abs() < 0.9 {
+                    Vector3::RIGHT
+                } else {
+                    Vector3::UP
+                };
+                x_axis -= direction * x_axis.dot(direction);
+            }
+            let x_axis = x_axis.normalized();
+            let z_axis = x_axis.cross(direction);
+            let body_basis = Basis::from_cols(x_axis, direction, z_axis);
+            let body_transform = Transform3D::new(
+                body_basis,
+                bone_transform.origin + direction * (length * 0.5),
+            );
+            let shape = self.capsule_shape_create();
+            self.shape_set_data(
+                shape,
+                Vector2::new(radius, length.max(radius * 2.0)).to_variant(),
+            );
+            let body = self.body_create();
+            self.body_set_mode(body, BodyMode::RIGID);
+            self.body_add_shape(body, shape, Transform3D::IDENTITY, false);
+            self.body_set_state(body, BodyState::TRANSFORM, body_transform.to_variant());
+            self.body_set_param(
+                body,
+                BodyParameter::MASS,
+                ragdoll_hint(&hint, "mass", 1.0).to_variant(),
+            );
+            self.body_set_space(body, space);
+            bodies.set(&bone_name, body);
+            shapes.set(&bone_name, shape);
+            bone_bodies[bone as usize] = Some((body, body_transform));
+            let mut ancestor = parent;
+            while ancestor >= 0 && bone_bodies[ancestor as usize].is_none() {
+                ancestor = skeleton.get_bone_parent(ancestor);
+            }
+            let Some((parent_body, parent_transform)) = (ancestor >= 0)
+                .then(|| bone_bodies[ancestor as usize])
+                .flatten()
+            else {
+                continue;
+            };
+            // Cone twist joints twist around X, so put X along the bone.
+            let joint_transform = Transform3D::new(
+                Basis::from_cols(direction, z_axis, x_axis),
+                bone_transform.origin,
+            );
+            let joint = self.joint_create();
+            self.joint_make_cone_twist(
+                joint,
+                parent_body,
+                parent_transform.affine_inverse() * joint_transform,
+                body,
+                body_transform.affine_inverse() * joint_transform,
+            );
+            self.cone_twist_joint_set_param(
+                joint,
+                ConeTwistJointParam::SWING_SPAN,
+                ragdoll_hint(&hint, "swing_span", std::f32::consts::FRAC_PI_4),
+            );
+            self.cone_twist_joint_set_param(
+                joint,
+                ConeTwistJointParam::TWIST_SPAN,
+                ragdoll_hint(&hint, "twist_span", std::f32::consts::FRAC_PI_6),
+            );
+            if joint_type != RapierJointType::Impulse {
+                self.joint_change_type(joint, joint_type);
+            }
+            joints.set(&bone_name, joint);
+        }
+        let mut result = VarDictionary::new();
+        result.set("bodies", &bodies);
+        result.set("shapes", &shapes);
+        result.set("joints", &joints);
+        result
+    }
+
+    /// Builds a chain of capsule bodies along `points`, one segment per pair of consecutive
+    /// points. Segments are linked by multibody pin joints, and the two ends are pinned to
+    /// `attach_a` and `attach_b` with impulse joints when those are bodies. A positive
+    /// `stiffness` adds motors holding the initial bend of every link.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn space_create_rope(
+        &mut self,
+        space: Rid,
+        points: PackedVectorArray,
+        segment_radius: real,
+        segment_mass: real,
+        stiffness: real,
+        attach_a: Rid,
+        attach_b: Rid,
+    ) -> VarDictionary {
+        let mut segments = Array::<Rid>::new();
+        let mut shapes = Array::<Rid>::new();
+        let mut joints = Array::<Rid>::new();
+        let points = points.as_slice();
+        // Body and transform of the previous segment.
+        let mut previous: Option<(Rid, Transform)> = None;
+        for pair in points.windows(2) {
+            let (start, end) = (pair[0], pair[1]);
+            let offset = end - start;
+            let length = offset.length();
+            if length <= 0.0 {
+                continue;
+            }
+            let direction = offset / length;
+            let center = start + offset * 0.5;
+            // Capsules are built along Y, so align Y with the segment.
+            #[cfg(feature = "dim2")]
+            let transform =
+                Transform2D::from_cols(Vector2::new(direction.y, -direction.x), direction, center);
+            #[cfg(feature = "dim3")]
+            let transform = {
+                let mut x_axis = if direction.x.abs() < 0.9 {
+                    Vector3::RIGHT
+                } else {
+                    Vector3::UP
+                };
+                x_axis -= direction * x_axis.dot(direction);
+                let x_axis = x_axis.normalized();
+                Transform3D::new(
+                    Basis::from_cols(x_axis, direction, x_axis.cross(direction)),
+                    center,
+                )
+            };
+            let shape = self.capsule_shape_create();
+            self.shape_set_data(
+                shape,
+                Vector2::new(segment_radius, length.max(segment_radius * 2.0)).to_variant(),
+            );
+            let body = self.body_create();
+            self.body_set_mode(body, BodyMode::RIGID);
+            self.body_add_shape(body, shape, Transform::IDENTITY, false);
+            self.body_set_state(body, BodyState::TRANSFORM, transform.to_variant());
+            self.body_set_param(body, BodyParameter::MASS, segment_mass.to_variant());
+            self.body_set_space(body, space);
+            segments.push(body);
+            shapes.push(shape);
+            let joint = match previous {
+                Some((previous_body, previous_transform)) => {
+                    let joint =
+                        self.rope_pin(previous_body, previous_transform, body, transform, start);
+                    self.joint_change_type(joint, RapierJointType::MultiBody);
+                    if stiffness > 0.0 {
+                        self.rope_hold_bend(joint, previous_transform, transform, stiffness);
+                    }
+                    Some(joint)
+                }
+                None if physics_data().collision_objects.contains_key(&attach_a) => {
+                    let attach_transform = self.body_get_state(attach_a, BodyState::TRANSFORM);
+                    Some(self.rope_pin(
+                        attach_a,
+                        attach_transform.try_to().unwrap_or_default(),
+                        body,
+                        transform,
+                        start,
+                    ))
+                }
+                None => None,
+            };
+            if let Some(joint) = joint {
+                joints.push(joint);
+            }
+            previous = Some((body, transform));
+        }
+        if let Some((last_body, last_transform)) = previous
+            && let Some(end) = points.last()
+            && physics_data().collision_objects.contains_key(&attach_b)
+        {
+            let attach_transform = self.body_get_state(attach_b, BodyState::TRANSFORM);
+            joints.push(self.rope_pin(
+                last_body,
+                last_transform,
+                attach_b,
+                attach_transform.try_to().unwrap_or_default(),
+                *end,
+            ));
+        }
+        let mut result = VarDictionary::new();
+        result.set("segments", &segments);
+        result.set("shapes", &shapes);
+        result.set("joints", &joints);
+        result
+    }
+
+    /// Pins `body_b` to `body_a` at the world space `anchor`.
+    #[cfg_attr(feature = "dim2", allow(unused_variables))]
+    fn rope_pin(
+        &mut self,
+        body_a: Rid,
+        transform_a: Transform,
+        body_b: Rid,
+        transform_b: Transform,
+        anchor: Vector,
+    ) -> Rid {
+        let joint = self.joint_create();
+        #[cfg(feature = "dim2")]
+        self.joint_make_pin(joint, anchor, body_a, body_b);
+        #[cfg(feature = "dim3")]
+        self.joint_make_pin(
+            joint,
+            body_a,
+            transform_a.affine_inverse() * anchor,
+            body_b,
+            transform_b.affine_inverse() * anchor,
+        );
+        joint
+    }
+
+    /// Drives a rope link back towards the relative rotation its two segments were created with.
+    fn rope_hold_bend(
+        &mut self,
+        joint: Rid,
+        transform_a: Transform,
+        transform_b: Transform,
+        stiffness: real,
+    ) {
+        let physics_data = physics_data();
+        let damping = stiffness * 0.1;
+        #[cfg(feature = "dim2")]
+        if let Some(RapierJoint::RapierRevoluteJoint(revolute)) =
+            physics_data.joints.get_mut(&joint)
+        {
+            revolute.set_motor_position_options(
+                &mut physics_data.physics_engine,
+                transform_a.b.angle_to(transform_b.b),
+                stiffness,
+                damping,
+                true,
+            );
+        }
+        #[cfg(feature = "dim3")]
+        if let Some(joint) = physics_data.joints.get(&joint) {
+            physics_data.physics_engine.joint_set_target_rotation(
+                joint.get_base().get_space_id(),
+                joint.get_base().get_handle(),
+                basis_to_rapier(transform_a.basis.inverse() * transform_b.basis),
+                stiffness,
+                damping,
+            );
+        }
+    }
+
     pub(super) fn free_rid(&mut self, rid: Rid) {
         let physics_data = physics_data();
         let mut space_to_reset = Rid::Invalid;
@@ -2596,6 +3145,12 @@ impl RapierPhysicsServerImpl {
             joint
                 .get_mut_base()
                 .destroy_joint(&mut physics_data.physics_engine);
+            RapierSpace::update_breakable_joint(
+                joint.get_base().get_id(),
+                None,
+                &mut physics_data.spaces,
+                &physics_data.ids,
+            );
             remove_id_rid(joint.get_base().get_id(), &mut physics_data.ids);
         } else if let Some(mut fluid) = physics_data.fluids.remove(&rid) {
             fluid.destroy_fluid(&mut physics_data.physics_engine);
@@ -2717,3 +3272,10 @@ impl RapierPhysicsServerImpl {
         0
     }
 }
+#[cfg(feature = "dim3")]
+fn ragdoll_hint<T: FromGodot>(hint: &Option<VarDictionary>, key: &str, default: T) -> T {
+    hint.as_ref()
+        .and_then(|hint| hint.get(key))
+        .and_then(|value| value.try_to::<T>().ok())
+        .unwrap_or(default)
+}
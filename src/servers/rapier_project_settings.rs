@@ -3,6 +3,8 @@ use godot::prelude::*;
 use godot::register::info::PropertyHint;
 use rapier::dynamics::IntegrationParameters;
 use rapier::math::Real;
+#[cfg(feature = "dim3")]
+use rapier::prelude::TriMeshFlags;
 /// Worker count for the solver: performance cores only.
 ///
 /// A solver step is a chain of barrier-synchronised stages, so each stage runs at the speed
@@ -84,6 +86,13 @@ const MOTION_STUCK_PENETRATION: &str = "physics/rapier/motion/stuck_penetration_
 const QUERY_MAX_SHAPE_CAST_RESULTS: &str = "physics/rapier/queries/max_shape_cast_results";
 const QUERY_POINT_HONORS_PICKABLE: &str = "physics/rapier/queries/point_query_honors_pickable";
 const SHAPE_SCALE_SUBDIVISIONS: &str = "physics/rapier/shapes/scale_subdivisions";
+const SHAPE_CONVEX_DECOMPOSITION: &str =
+    "physics/rapier/shapes/convex_decomposition_on_dynamic_bodies";
+#[cfg(feature = "dim3")]
+const SHAPE_TRIMESH_FLAGS: &str = "physics/rapier/shapes/trimesh_flags";
+// One name per bit of parry's TriMeshFlags, in bit order.
+#[cfg(feature = "dim3")]
+const SHAPE_TRIMESH_FLAGS_HINT: &str = "Half Edge Topology,Connected Components,Delete Bad Topology Triangles,Oriented,Merge Duplicate Vertices,Delete Degenerate Triangles,Delete Duplicate Triangles,Fix Internal Edges";
 const MOTION_RECOVER_ATTEMPTS_DEFAULT: i32 = 4;
 const MOTION_CAST_ITERATIONS_DEFAULT: i32 = 8;
 const MAX_SHAPE_CAST_RESULTS_DEFAULT: usize = 64;
@@ -137,6 +146,12 @@ pub struct MotionSettings {
     pub max_shape_cast_results: usize,
     pub point_query_honors_pickable: bool,
     pub shape_scale_subdivisions: u32,
+    /// Whether concave shapes on dynamic bodies collide as their convex decomposition. Shapes
+    /// can override it with an extra param.
+    pub convex_decomposition: bool,
+    /// Flags trimeshes are built with, unless a shape overrides them with an extra param.
+    #[cfg(feature = "dim3")]
+    pub trimesh_flags: TriMeshFlags,
 }
 
 impl Default for MotionSettings {
@@ -149,6 +164,9 @@ impl Default for MotionSettings {
             max_shape_cast_results: MAX_SHAPE_CAST_RESULTS_DEFAULT,
             point_query_honors_pickable: true,
             shape_scale_subdivisions: SHAPE_SCALE_SUBDIVISIONS_DEFAULT,
+            convex_decomposition: false,
+            #[cfg(feature = "dim3")]
+            trimesh_flags: TriMeshFlags::FIX_INTERNAL_EDGES,
         }
     }
 }
@@ -195,6 +213,14 @@ pub fn motion_settings() -> &'static MotionSettings {
                 SHAPE_SCALE_SUBDIVISIONS,
             )
             .max(4) as u32,
+            convex_decomposition: ProjectSettings::singleton()
+                .get_setting_with_override(SHAPE_CONVEX_DECOMPOSITION)
+                .try_to()
+                .unwrap_or(false),
+            #[cfg(feature = "dim3")]
+            trimesh_flags: trimesh_flags_from_bits(RapierProjectSettings::get_setting_int(
+                SHAPE_TRIMESH_FLAGS,
+            )),
         }
     })
 }
@@ -238,6 +264,17 @@ pub fn register_setting_ranged(
         p_hint_string,
     );
 }
+/// `FIX_INTERNAL_EDGES` spans two bits in parry but is a single flag in the editor, so setting
+/// its own bit also sets the vertex merging it depends on.
+#[cfg(feature = "dim3")]
+pub fn trimesh_flags_from_bits(bits: i64) -> TriMeshFlags {
+    let flags = TriMeshFlags::from_bits_truncate(bits as u16);
+    if flags.intersects(TriMeshFlags::FIX_INTERNAL_EDGES - TriMeshFlags::MERGE_DUPLICATE_VERTICES) {
+        flags | TriMeshFlags::FIX_INTERNAL_EDGES
+    } else {
+        flags
+    }
+}
 #[derive(Debug)]
 pub struct RapierProjectSettings;
 impl RapierProjectSettings {
@@ -304,6 +341,21 @@ impl RapierProjectSettings {
             "4,64,1,or_greater",
             false,
         );
+        register_setting(
+            SHAPE_CONVEX_DECOMPOSITION,
+            Variant::from(false),
+            true,
+            PropertyHint::NONE,
+            "",
+        );
+        #[cfg(feature = "dim3")]
+        register_setting(
+            SHAPE_TRIMESH_FLAGS,
+            Variant::from(TriMeshFlags::FIX_INTERNAL_EDGES.bits() as i64),
+            true,
+            PropertyHint::FLAGS,
+            SHAPE_TRIMESH_FLAGS_HINT,
+        );
         register_setting_ranged(
             SOLVER_MAX_CCD_SUBSTEPS,
             Variant::from(DEFAULT_MAX_CCD_SUBSTEPS),
@@ -0,0 +1,282 @@
+use godot::classes::physics_server_3d::*;
+use godot::prelude::*;
+use rapier::prelude::Pose;
+
+use super::rapier_shape::RapierShape;
+use crate::rapier_wrapper::prelude::*;
+use crate::servers::rapier_physics_singleton::PhysicsShapes;
+use crate::servers::rapier_physics_singleton::RapierId;
+use crate::shapes::rapier_shape::*;
+use crate::shapes::rapier_shape_base::RapierShapeBase;
+use crate::types::*;
+/// A parry primitive Godot has no shape resource for. The data is a dictionary whose `type` is
+/// one of:
+/// - `cone`, with the `height` and base `radius` of a cone along the y axis.
+/// - `triangle`, with its corners `a`, `b` and `c`.
+/// - `segment`, from `a` to `b`.
+/// - `capsule`, from `a` to `b`, with either one `radius` or a `radius_a` and a `radius_b`.
+/// - `compound`, with an array of `shapes`, each a dictionary of any other type with an
+///   optional `transform` placing it in the compound.
+///
+/// The data read back is rebuilt from the primitive, so it survives a state import.
+pub struct RapierCustomShape {
+    base: RapierShapeBase,
+}
+impl RapierCustomShape {
+    pub fn create(id: RapierId, rid: Rid, physics_shapes: &mut PhysicsShapes) {
+        let shape = Self {
+            base: RapierShapeBase::new(id, rid),
+        };
+        physics_shapes.insert(rid, RapierShape::RapierCustomShape(shape));
+    }
+}
+fn get_vector(dictionary: &VarDictionary, key: &str) -> Result<Vector, String> {
+    dictionary
+        .get(key)
+        .and_then(|value| value.try_to::<Vector>().ok())
+        .ok_or_else(|| format!("'{key}' must be a Vector3"))
+}
+fn get_dimension(dictionary: &VarDictionary, key: &str) -> Result<real, String> {
+    dictionary
+        .get(key)
+        .and_then(|value| value.try_to::<real>().ok())
+        .filter(|value| is_valid_shape_dimension(*value))
+        .ok_or_else(|| format!("'{key}' must be finite and positive"))
+}
+fn primitive_from_dictionary(
+    dictionary: &VarDictionary,
+    in_compound: bool,
+) -> Result<PrimitiveShape, String> {
+    let shape_type = dictionary
+        .get("type")
+        .and_then(|value| value.try_to::<GString>().ok())
+        .ok_or_else(|| "'type' must be a string".to_string())?;
+    match shape_type.to_string().as_str() {
+        "cone" => Ok(PrimitiveShape::Cone {
+            half_height: get_dimension(dictionary, "height")? / 2.0,
+            radius: get_dimension(dictionary, "radius")?,
+        }),
+        "triangle" => Ok(PrimitiveShape::Triangle(
+            vector_to_rapier(get_vector(dictionary, "a")?),
+            vector_to_rapier(get_vector(dictionary, "b")?),
+            vector_to_rapier(get_vector(dictionary, "c")?),
+        )),
+        "segment" => Ok(PrimitiveShape::Segment(
+            vector_to_rapier(get_vector(dictionary, "a")?),
+            vector_to_rapier(get_vector(dictionary, "b")?),
+        )),
+        "capsule" => {
+            let (radius_a, radius_b) = if dictionary.contains_key("radius") {
+                let radius = get_dimension(dictionary, "radius")?;
+                (radius, radius)
+            } else {
+                (
+                    get_dimension(dictionary, "radius_a")?,
+                    get_dimension(dictionary, "radius_b")?,
+                )
+            };
+            Ok(PrimitiveShape::Capsule {
+                a: vector_to_rapier(get_vector(dictionary, "a")?),
+                b: vector_to_rapier(get_vector(dictionary, "b")?),
+                radius_a,
+                radius_b,
+            })
+        }
+        "compound" if in_compound => Err("a compound cannot contain compounds".to_string()),
+        "compound" => {
+            let shapes = dictionary
+                .get("shapes")
+                .and_then(|value| value.try_to::<VarArray>().ok())
+                .filter(|shapes| !shapes.is_empty())
+                .ok_or_else(|| "'shapes' must be a non-empty array".to_string())?;
+            let mut parts = Vec::with_capacity(shapes.len());
+            for shape in shapes.iter_shared() {
+                let shape = shape
+                    .try_to::<VarDictionary>()
+                    .map_err(|_| "'shapes' must only hold dictionaries".to_string())?;
+                let transform = match shape.get("transform") {
+                    Some(transform) => transform
+                        .try_to::<Transform>()
+                        .map_err(|_| "'transform' must be a Transform3D".to_string())?,
+                    None => Transform::IDENTITY,
+                };
+                let position = Pose::from_parts(
+                    vector_to_rapier(transform.origin),
+                    basis_to_rapier(transform.basis),
+                );
+                parts.push((position, primitive_from_dictionary(&shape, true)?));
+            }
+            Ok(PrimitiveShape::Compound(parts))
+        }
+        other => Err(format!("unknown type '{other}'")),
+    }
+}
+fn primitive_to_dictionary(primitive: &PrimitiveShape) -> VarDictionary {
+    let mut dictionary = VarDictionary::new();
+    match primitive {
+        PrimitiveShape::Cone {
+            half_height,
+            radius,
+        } => {
+            dictionary.set("type", "cone");
+            dictionary.set("height", *half_height * 2.0);
+            dictionary.set("radius", *radius);
+        }
+        PrimitiveShape::Triangle(a, b, c) => {
+            dictionary.set("type", "triangle");
+            dictionary.set("a", vector_to_godot(*a));
+            dictionary.set("b", vector_to_godot(*b));
+            dictionary.set("c", vector_to_godot(*c));
+        }
+        PrimitiveShape::Segment(a, b) => {
+            dictionary.set("type", "segment");
+            dictionary.set("a", vector_to_godot(*a));
+            dictionary.set("b", vector_to_godot(*b));
+        }
+        PrimitiveShape::Capsule {
+            a,
+            b,
+            radius_a,
+            radius_b,
+        } => {
+            dictionary.set("type", "capsule");
+            dictionary.set("a", vector_to_godot(*a));
+            dictionary.set("b", vector_to_godot(*b));
+            if radius_a == radius_b {
+                dictionary.set("radius", *radius_a);
+            } else {
+                dictionary.set("radius_a", *radius_a);
+                dictionary.set("radius_b", *radius_b);
+            }
+        }
+        PrimitiveShape::Compound(parts) => {
+            let mut shapes = VarArray::new();
+            for (position, part) in parts {
+                let mut shape = primitive_to_dictionary(part);
+                if *position != Pose::IDENTITY {
+                    let transform = transform_update(
+                        &Transform::IDENTITY,
+                        position.rotation,
+                        vector_to_godot(position.translation),
+                    );
+                    shape.set("transform", transform);
+                }
+                shapes.push(&shape.to_variant());
+            }
+            dictionary.set("type", "compound");
+            dictionary.set("shapes", &shapes);
+        }
+    }
+    dictionary
+}
+impl IRapierShape for RapierCustomShape {
+    fn get_base(&self) -> &RapierShapeBase {
+        &self.base
+    }
+
+    fn get_mut_base(&mut self) -> &mut RapierShapeBase {
+        &mut self.base
+    }
+
+    fn get_type(&self) -> ShapeType {
+        ShapeType::CUSTOM
+    }
+
+    fn allows_one_way_collision(&self) -> bool {
+        true
+    }
+
+    fn set_data(&mut self, data: Variant, physics_engine: &mut PhysicsEngine) {
+        let Ok(dictionary) = data.try_to::<VarDictionary>() else {
+            godot_error!("RapierCustomShape data must be a dictionary. Got {}", data);
+            return;
+        };
+        let primitive = match primitive_from_dictionary(&dictionary, false) {
+            Ok(primitive) => primitive,
+            Err(error) => {
+                godot_error!("RapierCustomShape data is invalid: {}. Got {}", error, data);
+                return;
+            }
+        };
+        if !physics_engine.shape_create_primitive(&primitive, self.base.get_id()) {
+            godot_error!("RapierCustomShape could not be built. Got {}", data);
+            return;
+        }
+        self.base.reset_aabb(physics_engine);
+    }
+
+    fn get_data(&self, physics_engine: &PhysicsEngine) -> Variant {
+        physics_engine
+            .shape_get_primitive(self.base.get_id())
+            .map(primitive_to_dictionary)
+            .unwrap_or_default()
+            .to_variant()
+    }
+}
+#[cfg(feature = "test")]
+mod tests {
+    use godot::prelude::*;
+
+    use super::*;
+    use crate::servers::rapier_physics_singleton::PhysicsShapes;
+    use crate::servers::rapier_physics_singleton::physics_data;
+    use crate::shapes::rapier_shape::IRapierShape;
+    #[derive(GodotClass)]
+    #[class(base=Object, init)]
+    pub struct RapierCustomShapeTests {}
+    #[godot_api]
+    impl RapierCustomShapeTests {
+        #[func]
+        fn test_create() {
+            let mut physics_shapes = PhysicsShapes::new();
+            let rid = Rid::new(123);
+            RapierCustomShape::create(0, rid, &mut physics_shapes);
+            assert!(physics_shapes.contains_key(&rid));
+            match physics_shapes.get(&rid) {
+                Some(RapierShape::RapierCustomShape(_)) => {}
+                _ => panic!("Shape was not inserted correctly"),
+            }
+            let custom_shape = physics_shapes.get(&rid).unwrap();
+            assert_eq!(custom_shape.get_type(), ShapeType::CUSTOM);
+            assert!(custom_shape.allows_one_way_collision());
+        }
+
+        #[func]
+        fn test_set_data() {
+            let mut custom_shape = RapierCustomShape {
+                base: RapierShapeBase::new(RapierId::default(), Rid::Invalid),
+            };
+            let mut cone = VarDictionary::new();
+            cone.set("type", "cone");
+            cone.set("height", 2.0);
+            cone.set("radius", 0.5);
+            let mut capsule = VarDictionary::new();
+            capsule.set("type", "capsule");
+            capsule.set("a", Vector3::ZERO);
+            capsule.set("b", Vector3::new(0.0, 1.0, 0.0));
+            capsule.set("radius_a", 0.5);
+            capsule.set("radius_b", 0.25);
+            capsule.set("transform", Transform3D::IDENTITY.translated(Vector3::UP));
+            let mut compound = VarDictionary::new();
+            compound.set("type", "compound");
+            compound.set("shapes", &varray![&cone, &capsule]);
+            custom_shape.set_data(compound.to_variant(), &mut physics_data().physics_engine);
+            let data: VarDictionary = custom_shape
+                .get_data(&physics_data().physics_engine)
+                .try_to()
+                .unwrap();
+            assert_eq!(data, compound);
+            let shape = physics_data()
+                .physics_engine
+                .get_shape(custom_shape.get_base().get_id())
+                .unwrap();
+            let parts = shape.as_compound().unwrap().shapes();
+            assert_eq!(parts.len(), 2);
+            assert_eq!(parts[0].0, Pose::IDENTITY);
+            assert_eq!(parts[1].0.translation, vector_to_rapier(Vector3::UP));
+            custom_shape
+                .get_mut_base()
+                .destroy_shape(&mut physics_data().physics_engine);
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use godot::classes::physics_server_2d::*;
+use godot::prelude::*;
+
+use super::rapier_shape::RapierShape;
+use crate::rapier_wrapper::prelude::*;
+use crate::servers::rapier_physics_singleton::PhysicsShapes;
+use crate::servers::rapier_physics_singleton::RapierId;
+use crate::shapes::rapier_shape::*;
+use crate::shapes::rapier_shape_base::RapierShapeBase;
+use crate::types::*;
+/// A row of `heights` spaced `spacing` apart horizontally and centered on the origin. The heights
+/// are y coordinates, so larger values are lower on screen.
+pub struct RapierHeightMapShape2D {
+    base: RapierShapeBase,
+}
+impl_rapier_shape_create!(RapierHeightMapShape2D, RapierHeightMapShape2D);
+impl IRapierShape for RapierHeightMapShape2D {
+    fn get_base(&self) -> &RapierShapeBase {
+        &self.base
+    }
+
+    fn get_mut_base(&mut self) -> &mut RapierShapeBase {
+        &mut self.base
+    }
+
+    fn get_type(&self) -> ShapeType {
+        ShapeType::CONCAVE_POLYGON
+    }
+
+    fn allows_one_way_collision(&self) -> bool {
+        true
+    }
+
+    fn set_data(&mut self, data: Variant, physics_engine: &mut PhysicsEngine) {
+        let Ok(dictionary) = data.try_to::<VarDictionary>() else {
+            godot_error!(
+                "RapierHeightMapShape2D data must be a dictionary. Got {}",
+                data
+            );
+            return;
+        };
+        let (Some(Ok(heights)), Some(spacing)) = (
+            dictionary
+                .get("heights")
+                .map(|v| v.try_to::<PackedFloatArray>()),
+            dictionary.get("spacing").map(|v| variant_to_float(&v)),
+        ) else {
+            godot_error!(
+                "RapierHeightMapShape2D data must be a dictionary with 'heights' and 'spacing' keys. Got {}",
+                data
+            );
+            return;
+        };
+        if !is_valid_shape_dimension(spacing) {
+            godot_error!(
+                "RapierHeightMapShape2D spacing must be finite and positive. Got {}",
+                spacing
+            );
+            return;
+        }
+        if heights.len() < 2 {
+            godot_error!("RapierHeightMapShape2D must have at least 2 heights");
+            return;
+        }
+        if !heights.as_slice().iter().all(|h| h.is_finite()) {
+            godot_error!("RapierHeightMapShape2D heights must be finite");
+            return;
+        }
+        physics_engine.shape_create_heightfield(heights.as_slice(), spacing, self.base.get_id());
+        self.base.reset_aabb(physics_engine);
+    }
+
+    fn get_data(&self, physics_engine: &PhysicsEngine) -> Variant {
+        let (heights, spacing) = physics_engine.shape_get_heightfield(self.base.get_id());
+        let mut dictionary = VarDictionary::new();
+        dictionary.set("heights", &PackedFloatArray::from(heights.as_slice()));
+        dictionary.set("spacing", spacing);
+        dictionary.to_variant()
+    }
+}
+#[cfg(feature = "test")]
+mod tests {
+    use godot::prelude::*;
+
+    use super::*;
+    use crate::servers::rapier_physics_singleton::PhysicsShapes;
+    use crate::servers::rapier_physics_singleton::physics_data;
+    use crate::shapes::rapier_shape::IRapierShape;
+    #[derive(GodotClass)]
+    #[class(base=Object, init)]
+    pub struct RapierHeightMapShape2DTests {}
+    #[godot_api]
+    impl RapierHeightMapShape2DTests {
+        #[func]
+        fn test_create() {
+            let mut physics_shapes = PhysicsShapes::new();
+            let rid = Rid::new(123);
+            RapierHeightMapShape2D::create(0, rid, &mut physics_shapes);
+            assert!(physics_shapes.contains_key(&rid));
+            match physics_shapes.get(&rid) {
+                Some(RapierShape::RapierHeightMapShape2D(_)) => {}
+                _ => panic!("Shape was not inserted correctly"),
+            }
+            let heightmap_shape = physics_shapes.get(&rid).unwrap();
+            assert_eq!(heightmap_shape.get_type(), ShapeType::CONCAVE_POLYGON);
+            assert!(heightmap_shape.allows_one_way_collision());
+        }
+
+        #[func]
+        fn test_set_data() {
+            let mut heightmap_shape = RapierHeightMapShape2D {
+                base: RapierShapeBase::new(RapierId::default(), Rid::Invalid),
+            };
+            let heights = PackedFloatArray::from(&[0.0, -1.0, 2.0][..]);
+            let mut dict = VarDictionary::new();
+            dict.set("heights", &heights);
+            dict.set("spacing", 4.0);
+            heightmap_shape.set_data(dict.to_variant(), &mut physics_data().physics_engine);
+            let data: VarDictionary = heightmap_shape
+                .get_data(&physics_data().physics_engine)
+                .try_to()
+                .unwrap();
+            assert_eq!(
+                data.get("heights").unwrap().to::<PackedFloatArray>(),
+                heights
+            );
+            assert_eq!(data.get("spacing").unwrap().to::<real>(), 4.0);
+            let aabb = heightmap_shape.get_base().get_aabb(Vector2::ZERO);
+            assert_eq!(aabb.position, Vector2::new(-4.0, -1.0));
+            assert_eq!(aabb.size, Vector2::new(8.0, 3.0));
+            heightmap_shape
+                .get_mut_base()
+                .destroy_shape(&mut physics_data().physics_engine);
+        }
+    }
+}
@@ -30,10 +30,38 @@ fn heights_from_image(image: &Gd<Image>) -> Option<PackedFloatArray> {
         .collect();
     Some(PackedFloatArray::from(heights.as_slice()))
 }
+/// Besides Godot's `width`, `depth` and `heights`, the data may hold `holes`, one byte per cell
+/// with nonzero marking a removed cell. NaN heights also remove the cells around them.
 pub struct RapierHeightMapShape3D {
     base: RapierShapeBase,
 }
 impl_rapier_shape_create!(RapierHeightMapShape3D, RapierHeightMapShape3D);
+impl RapierHeightMapShape3D {
+    /// Replaces the heights of a `width` by `depth` region starting at column `x` and row `z`,
+    /// keeping the rest of the heightmap. Returns `false` if the region is out of bounds.
+    pub fn update_region(
+        &mut self,
+        x: i32,
+        z: i32,
+        width: i32,
+        depth: i32,
+        heights: &[real],
+        physics_engine: &mut PhysicsEngine,
+    ) -> bool {
+        if !physics_engine.shape_update_heightmap_region(
+            self.base.get_id(),
+            x,
+            z,
+            width,
+            depth,
+            heights,
+        ) {
+            return false;
+        }
+        self.base.reset_aabb(physics_engine);
+        true
+    }
+}
 impl IRapierShape for RapierHeightMapShape3D {
     fn get_base(&self) -> &RapierShapeBase {
         &self.base
@@ -55,6 +83,7 @@ impl IRapierShape for RapierHeightMapShape3D {
         let width;
         let depth;
         let heights;
+        let mut holes = Vec::new();
         if let Ok(dictionary) = data.try_to::<VarDictionary>() {
             let in_width = dictionary.get_or_nil("width");
             let in_depth = dictionary.get_or_nil("depth");
@@ -79,6 +108,13 @@ impl IRapierShape for RapierHeightMapShape3D {
                 width = in_width;
                 depth = in_depth;
                 heights = in_heights;
+                if let Some(in_holes) = dictionary.get("holes") {
+                    let Ok(in_holes) = in_holes.try_to::<PackedByteArray>() else {
+                        godot_error!("Heightmap holes must be a byte array. Got {}", in_holes);
+                        return;
+                    };
+                    holes = in_holes.as_slice().iter().map(|hole| *hole != 0).collect();
+                }
                 // Compute min and max heights or use precomputed values.
                 let mut min_height: real = 0.0;
                 let mut max_height: real = 0.0;
@@ -93,6 +129,9 @@ impl IRapierShape for RapierHeightMapShape3D {
                     let heights_size = heights.len();
                     for i in 0..heights_size {
                         let h = heights[i];
+                        if h.is_nan() {
+                            continue;
+                        }
                         if h < min_height {
                             min_height = h;
                         } else if h > max_height {
@@ -124,13 +163,23 @@ impl IRapierShape for RapierHeightMapShape3D {
             godot_error!("Invalid heightmap shape data");
             return;
         }
-        physics_engine.shape_create_heightmap(heights.as_slice(), width, depth, self.base.get_id());
+        if !holes.is_empty() && holes.len() != ((width - 1) * (depth - 1)) as usize {
+            godot_error!("Heightmap holes must hold one entry per cell");
+            return;
+        }
+        physics_engine.shape_create_heightmap(
+            heights.as_slice(),
+            width,
+            depth,
+            &holes,
+            self.base.get_id(),
+        );
         self.base.reset_aabb(physics_engine);
     }
 
     fn get_data(&self, physics_engine: &PhysicsEngine) -> Variant {
         let mut dictionary = VarDictionary::new();
-        let (heights, depth, width) = physics_engine.shape_get_heightmap(self.base.get_id());
+        let (heights, width, depth, holes) = physics_engine.shape_get_heightmap(self.base.get_id());
         let _ = dictionary.insert("width", width);
         let _ = dictionary.insert("depth", depth);
         let mut packed_heights = PackedFloatArray::default();
@@ -139,6 +188,10 @@ impl IRapierShape for RapierHeightMapShape3D {
         }
         let heights_variant = packed_heights.to_variant();
         let _ = dictionary.insert("heights", &heights_variant);
+        if holes.contains(&true) {
+            let holes: Vec<u8> = holes.into_iter().map(u8::from).collect();
+            let _ = dictionary.insert("holes", &PackedByteArray::from(holes.as_slice()));
+        }
         dictionary.to_variant()
     }
 }
@@ -9,13 +9,18 @@ use super::rapier_circle_shape::RapierCircleShape;
 use super::rapier_concave_polygon_shape::RapierConcavePolygonShape;
 use super::rapier_convex_polygon_shape::RapierConvexPolygonShape;
 #[cfg(feature = "dim3")]
+use super::rapier_custom_shape::RapierCustomShape;
+#[cfg(feature = "dim3")]
 use super::rapier_cylinder_shape_3d::RapierCylinderShape3D;
+#[cfg(feature = "dim2")]
+use super::rapier_heightmap_shape_2d::RapierHeightMapShape2D;
 #[cfg(feature = "dim3")]
 use super::rapier_heightmap_shape_3d::RapierHeightMapShape3D;
 use super::rapier_rectangle_shape::RapierRectangleShape;
 #[cfg(feature = "dim2")]
 use super::rapier_segment_shape_2d::RapierSegmentShape2D;
 use super::rapier_separation_ray_shape::RapierSeparationRayShape;
+use super::rapier_voxel_shape::RapierVoxelShape;
 use super::rapier_world_boundary_shape::RapierWorldBoundaryShape;
 use crate::rapier_wrapper::prelude::*;
 use crate::shapes::rapier_shape_base::RapierShapeBase;
@@ -53,13 +58,18 @@ pub enum RapierShape {
     RapierConcavePolygonShape(RapierConcavePolygonShape),
     RapierConvexPolygonShape(RapierConvexPolygonShape),
     #[cfg(feature = "dim3")]
+    RapierCustomShape(RapierCustomShape),
+    #[cfg(feature = "dim3")]
     RapierCylinderShape3D(RapierCylinderShape3D),
+    #[cfg(feature = "dim2")]
+    RapierHeightMapShape2D(RapierHeightMapShape2D),
     #[cfg(feature = "dim3")]
     RapierHeightMapShape3D(RapierHeightMapShape3D),
     RapierRectangleShape(RapierRectangleShape),
     #[cfg(feature = "dim2")]
     RapierSegmentShape2D(RapierSegmentShape2D),
     RapierSeparationRayShape(RapierSeparationRayShape),
+    RapierVoxelShape(RapierVoxelShape),
     RapierWorldBoundaryShape(RapierWorldBoundaryShape),
 }
 macro_rules! impl_rapier_shape_trait {
@@ -116,10 +126,12 @@ impl_rapier_shape_trait!(
     RapierCircleShape,
     RapierConcavePolygonShape,
     RapierConvexPolygonShape,
+    RapierCustomShape,
     RapierCylinderShape3D,
     RapierHeightMapShape3D,
     RapierRectangleShape,
     RapierSeparationRayShape,
+    RapierVoxelShape,
     RapierWorldBoundaryShape
 );
 #[cfg(feature = "dim2")]
@@ -129,8 +141,10 @@ impl_rapier_shape_trait!(
     RapierCircleShape,
     RapierConcavePolygonShape,
     RapierConvexPolygonShape,
+    RapierHeightMapShape2D,
     RapierSegmentShape2D,
     RapierRectangleShape,
     RapierSeparationRayShape,
+    RapierVoxelShape,
     RapierWorldBoundaryShape
 );
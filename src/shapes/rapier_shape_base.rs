@@ -2,7 +2,11 @@ use std::collections::BTreeMap;
 
 use godot::prelude::*;
 #[cfg(feature = "serde-serialize")]
+use rapier::math::Real;
+#[cfg(feature = "serde-serialize")]
 use rapier::prelude::SharedShape;
+#[cfg(all(feature = "serde-serialize", feature = "dim3"))]
+use rapier::prelude::TriMeshFlags;
 
 #[cfg(feature = "serde-serialize")]
 use crate::bodies::exportable_object::ExportToImport;
@@ -14,9 +18,13 @@ use crate::bodies::exportable_object::ImportToExport;
 use crate::bodies::exportable_object::ObjectImportState;
 use crate::bodies::rapier_collision_object::IRapierCollisionObject;
 use crate::rapier_wrapper::prelude::*;
+use crate::servers::rapier_physics_server_extra::RapierShapeParam;
 use crate::servers::rapier_physics_singleton::PhysicsData;
 use crate::servers::rapier_physics_singleton::RapierId;
 use crate::servers::rapier_physics_singleton::get_id_rid;
+use crate::servers::rapier_project_settings::motion_settings;
+#[cfg(feature = "dim3")]
+use crate::servers::rapier_project_settings::trimesh_flags_from_bits;
 use crate::types::*;
 #[cfg_attr(feature = "serde-serialize", derive(serde::Serialize))]
 #[derive(Debug)]
@@ -24,6 +32,12 @@ use crate::types::*;
 pub struct ShapeExport<'a> {
     state: &'a RapierShapeState,
     shape: &'a SharedShape,
+    convex_decomposition: Option<bool>,
+    #[cfg(feature = "dim3")]
+    trimesh_flags: Option<u16>,
+    border_radius: Real,
+    #[cfg(feature = "dim3")]
+    primitive: Option<&'a PrimitiveShape>,
 }
 #[cfg(feature = "serde-serialize")]
 impl<'a> ExportToImport for ShapeExport<'a> {
@@ -33,6 +47,12 @@ impl<'a> ExportToImport for ShapeExport<'a> {
         ShapeImport {
             state: self.state.clone(),
             shape: self.shape.clone(),
+            convex_decomposition: self.convex_decomposition,
+            #[cfg(feature = "dim3")]
+            trimesh_flags: self.trimesh_flags,
+            border_radius: self.border_radius,
+            #[cfg(feature = "dim3")]
+            primitive: self.primitive.cloned(),
         }
     }
 }
@@ -41,6 +61,16 @@ impl<'a> ExportToImport for ShapeExport<'a> {
 pub struct ShapeImport {
     state: RapierShapeState,
     shape: SharedShape,
+    #[serde(default)]
+    convex_decomposition: Option<bool>,
+    #[cfg(feature = "dim3")]
+    #[serde(default)]
+    trimesh_flags: Option<u16>,
+    #[serde(default)]
+    border_radius: Real,
+    #[cfg(feature = "dim3")]
+    #[serde(default)]
+    primitive: Option<PrimitiveShape>,
 }
 #[cfg(feature = "serde-serialize")]
 impl ImportToExport for ShapeImport {
@@ -50,6 +80,12 @@ impl ImportToExport for ShapeImport {
         ShapeExport {
             state: &self.state,
             shape: &self.shape,
+            convex_decomposition: self.convex_decomposition,
+            #[cfg(feature = "dim3")]
+            trimesh_flags: self.trimesh_flags,
+            border_radius: self.border_radius,
+            #[cfg(feature = "dim3")]
+            primitive: self.primitive.as_ref(),
         }
     }
 }
@@ -147,6 +183,48 @@ impl RapierShapeBase {
         self.state.id
     }
 
+    /// Owners have to recreate their colliders for a change to take effect.
+    pub fn set_extra_param(
+        &mut self,
+        param: RapierShapeParam,
+        value: Variant,
+        physics_engine: &mut PhysicsEngine,
+    ) {
+        match param {
+            RapierShapeParam::ConvexDecomposition => {
+                // Anything but a bool clears the override.
+                physics_engine.shape_set_convex_decomposition(self.get_id(), value.try_to().ok());
+            }
+            #[cfg(feature = "dim3")]
+            RapierShapeParam::TrimeshFlags => {
+                // Anything but an int clears the override.
+                physics_engine.shape_set_trimesh_flags(
+                    self.get_id(),
+                    value.try_to::<i64>().ok().map(trimesh_flags_from_bits),
+                );
+            }
+        }
+    }
+
+    pub fn get_extra_param(
+        &self,
+        param: RapierShapeParam,
+        physics_engine: &PhysicsEngine,
+    ) -> Variant {
+        match param {
+            RapierShapeParam::ConvexDecomposition => physics_engine
+                .shape_get_convex_decomposition(self.get_id())
+                .unwrap_or(motion_settings().convex_decomposition)
+                .to_variant(),
+            #[cfg(feature = "dim3")]
+            RapierShapeParam::TrimeshFlags => (physics_engine
+                .shape_get_trimesh_flags(self.get_id())
+                .unwrap_or(motion_settings().trimesh_flags)
+                .bits() as i64)
+                .to_variant(),
+        }
+    }
+
     pub fn destroy_shape(&mut self, physics_engine: &mut PhysicsEngine) {
         physics_engine.shape_destroy(self.get_id());
         self.state.owners.clear();
@@ -167,11 +245,24 @@ impl ExportableObject for RapierShapeBase {
         &'a self,
         physics_engine: &'a mut PhysicsEngine,
     ) -> Option<Self::ExportState<'a>> {
+        let convex_decomposition = physics_engine.shape_get_convex_decomposition(self.get_id());
+        #[cfg(feature = "dim3")]
+        let trimesh_flags = physics_engine
+            .shape_get_trimesh_flags(self.get_id())
+            .map(|flags| flags.bits());
+        let border_radius = physics_engine.shape_get_border_radius(self.get_id());
+        let physics_engine: &'a PhysicsEngine = physics_engine;
         physics_engine
             .get_shape(self.get_id())
             .map(|inner| ShapeExport {
                 state: &self.state,
                 shape: inner,
+                convex_decomposition,
+                #[cfg(feature = "dim3")]
+                trimesh_flags,
+                border_radius,
+                #[cfg(feature = "dim3")]
+                primitive: physics_engine.shape_get_primitive(self.get_id()),
             })
     }
 
@@ -180,6 +271,20 @@ impl ExportableObject for RapierShapeBase {
             crate::bodies::exportable_object::ObjectImportState::ShapeBase(shape_import) => {
                 self.state = shape_import.state;
                 physics_engine.insert_shape(shape_import.shape, self.get_id());
+                physics_engine.shape_set_convex_decomposition(
+                    self.get_id(),
+                    shape_import.convex_decomposition,
+                );
+                #[cfg(feature = "dim3")]
+                physics_engine.shape_set_trimesh_flags(
+                    self.get_id(),
+                    shape_import
+                        .trimesh_flags
+                        .map(TriMeshFlags::from_bits_truncate),
+                );
+                physics_engine.shape_set_border_radius(self.get_id(), shape_import.border_radius);
+                #[cfg(feature = "dim3")]
+                physics_engine.shape_set_primitive(self.get_id(), shape_import.primitive);
             }
             _ => {
                 godot_error!("Attempted to import invalid state data.");
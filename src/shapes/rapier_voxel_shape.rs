@@ -0,0 +1,177 @@
+#[cfg(feature = "dim2")]
+use godot::classes::physics_server_2d::*;
+#[cfg(feature = "dim3")]
+use godot::classes::physics_server_3d::*;
+use godot::prelude::*;
+use rapier::math::DIM;
+
+use super::rapier_shape::RapierShape;
+use crate::rapier_wrapper::prelude::*;
+use crate::servers::rapier_physics_singleton::PhysicsShapes;
+use crate::servers::rapier_physics_singleton::RapierId;
+use crate::shapes::rapier_shape::*;
+use crate::shapes::rapier_shape_base::RapierShapeBase;
+use crate::types::*;
+/// A grid of filled cells of `voxel_size` each. The data is a dictionary with the `voxel_size`
+/// and the `cells`, packed as consecutive integer grid coordinates.
+pub struct RapierVoxelShape {
+    base: RapierShapeBase,
+}
+impl_rapier_shape_create!(RapierVoxelShape, RapierVoxelShape);
+impl RapierVoxelShape {
+    /// Fills or clears a single cell of the shape. Colliders already using the shape keep their
+    /// own copy, see [`PhysicsEngine::collider_set_voxel`]. Returns whether the cell was filled
+    /// before the change.
+    pub fn set_voxel(
+        &mut self,
+        cell: Vectori,
+        filled: bool,
+        physics_engine: &mut PhysicsEngine,
+    ) -> bool {
+        let was_filled =
+            physics_engine.shape_set_voxel(self.base.get_id(), ivector_to_rapier(cell), filled);
+        self.base.reset_aabb(physics_engine);
+        was_filled
+    }
+}
+impl IRapierShape for RapierVoxelShape {
+    fn get_base(&self) -> &RapierShapeBase {
+        &self.base
+    }
+
+    fn get_mut_base(&mut self) -> &mut RapierShapeBase {
+        &mut self.base
+    }
+
+    fn get_type(&self) -> ShapeType {
+        ShapeType::CUSTOM
+    }
+
+    fn allows_one_way_collision(&self) -> bool {
+        false
+    }
+
+    fn set_data(&mut self, data: Variant, physics_engine: &mut PhysicsEngine) {
+        let Ok(dictionary) = data.try_to::<VarDictionary>() else {
+            godot_error!("RapierVoxelShape data must be a dictionary. Got {}", data);
+            return;
+        };
+        let (Some(Ok(voxel_size)), Some(Ok(cells))) = (
+            dictionary.get("voxel_size").map(|v| v.try_to::<Vector>()),
+            dictionary
+                .get("cells")
+                .map(|v| v.try_to::<PackedInt32Array>()),
+        ) else {
+            godot_error!(
+                "RapierVoxelShape data must be a dictionary with 'voxel_size' and 'cells' keys. Got {}",
+                data
+            );
+            return;
+        };
+        if !voxel_size
+            .to_array()
+            .into_iter()
+            .all(is_valid_shape_dimension)
+        {
+            godot_error!(
+                "RapierVoxelShape voxel_size must be finite and positive. Got {}",
+                voxel_size
+            );
+            return;
+        }
+        if !cells.len().is_multiple_of(DIM) {
+            godot_error!(
+                "RapierVoxelShape cells must hold {} coordinates per cell. Got {}",
+                DIM,
+                cells.len()
+            );
+            return;
+        }
+        let cells: Vec<_> = cells
+            .as_slice()
+            .chunks_exact(DIM)
+            .filter_map(|cell| <[i32; DIM]>::try_from(cell).ok())
+            .map(|cell| ivector_to_rapier(Vectori::from_array(cell)))
+            .collect();
+        physics_engine.shape_create_voxels(
+            vector_to_rapier(voxel_size),
+            &cells,
+            self.base.get_id(),
+        );
+        self.base.reset_aabb(physics_engine);
+    }
+
+    fn get_data(&self, physics_engine: &PhysicsEngine) -> Variant {
+        let (voxel_size, cells) = physics_engine.shape_get_voxels(self.base.get_id());
+        let cells: Vec<i32> = cells
+            .into_iter()
+            .flat_map(|cell| ivector_to_godot(cell).to_array())
+            .collect();
+        let mut dictionary = VarDictionary::new();
+        dictionary.set("voxel_size", vector_to_godot(voxel_size));
+        dictionary.set("cells", &PackedInt32Array::from(cells.as_slice()));
+        dictionary.to_variant()
+    }
+}
+#[cfg(feature = "test")]
+mod tests {
+    use godot::prelude::*;
+
+    use super::*;
+    use crate::servers::rapier_physics_singleton::PhysicsShapes;
+    use crate::servers::rapier_physics_singleton::physics_data;
+    use crate::shapes::rapier_shape::IRapierShape;
+    #[derive(GodotClass)]
+    #[class(base=Object, init)]
+    pub struct RapierVoxelShapeTests {}
+    #[godot_api]
+    impl RapierVoxelShapeTests {
+        #[func]
+        fn test_create() {
+            let mut physics_shapes = PhysicsShapes::new();
+            let rid = Rid::new(123);
+            RapierVoxelShape::create(0, rid, &mut physics_shapes);
+            assert!(physics_shapes.contains_key(&rid));
+            match physics_shapes.get(&rid) {
+                Some(RapierShape::RapierVoxelShape(_)) => {}
+                _ => panic!("Shape was not inserted correctly"),
+            }
+            let voxel_shape = physics_shapes.get(&rid).unwrap();
+            assert_eq!(voxel_shape.get_type(), ShapeType::CUSTOM);
+            assert!(!voxel_shape.allows_one_way_collision());
+        }
+
+        #[func]
+        fn test_set_data() {
+            let mut voxel_shape = RapierVoxelShape {
+                base: RapierShapeBase::new(RapierId::default(), Rid::Invalid),
+            };
+            // The cells at the origin and at one along every axis.
+            let cells: Vec<i32> = (0..DIM * 2).map(|i| (i / DIM) as i32).collect();
+            let mut dict = VarDictionary::new();
+            dict.set("voxel_size", Vector::ONE);
+            dict.set("cells", &PackedInt32Array::from(cells.as_slice()));
+            voxel_shape.set_data(dict.to_variant(), &mut physics_data().physics_engine);
+            let data = voxel_shape.get_data(&physics_data().physics_engine);
+            let data: VarDictionary = data.try_to().unwrap();
+            let data_cells: PackedInt32Array = data.get("cells").unwrap().to();
+            assert_eq!(data_cells.len(), DIM * 2);
+            assert_eq!(data.get("voxel_size").unwrap().to::<Vector>(), Vector::ONE);
+            assert!(voxel_shape.set_voxel(Vectori::ONE, false, &mut physics_data().physics_engine));
+            assert!(!voxel_shape.set_voxel(
+                Vectori::ONE,
+                false,
+                &mut physics_data().physics_engine
+            ));
+            let data: VarDictionary = voxel_shape
+                .get_data(&physics_data().physics_engine)
+                .try_to()
+                .unwrap();
+            let data_cells: PackedInt32Array = data.get("cells").unwrap().to();
+            assert_eq!(data_cells.len(), DIM);
+            voxel_shape
+                .get_mut_base()
+                .destroy_shape(&mut physics_data().physics_engine);
+        }
+    }
+}
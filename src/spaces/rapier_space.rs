@@ -18,6 +18,7 @@ use rapier::geometry::NarrowPhase;
 use servers::rapier_physics_singleton::PhysicsCollisionObjects;
 use servers::rapier_physics_singleton::PhysicsData;
 use servers::rapier_physics_singleton::PhysicsIds;
+use servers::rapier_physics_singleton::PhysicsJoints;
 use servers::rapier_physics_singleton::PhysicsSpaces;
 use servers::rapier_physics_singleton::RapierId;
 use servers::rapier_physics_singleton::get_id_rid;
@@ -34,12 +35,16 @@ use crate::bodies::exportable_object::ImportToExport;
 #[cfg(feature = "serde-serialize")]
 use crate::bodies::exportable_object::ObjectImportState;
 use crate::bodies::rapier_collision_object::*;
+use crate::joints::rapier_joint::IRapierJoint;
+use crate::joints::rapier_joint_base::RapierJointBase;
+use crate::rapier_wrapper::joint::JointCoupling;
 use crate::rapier_wrapper::prelude::*;
 use crate::servers::rapier_physics_singleton::physics_data;
 use crate::servers::rapier_project_settings::*;
 use crate::types::*;
 use crate::*;
-
+/// Passes over all couplings of a space per step.
+const COUPLING_ITERATIONS: usize = 4;
 enum PendingQueryCallback {
     BodyStateSync {
         rid: Rid,
@@ -554,6 +559,11 @@ impl RapierSpace {
             vector_to_rapier(default_gravity_dir) * default_gravity_value;
         settings.pixel_gravity = vector_to_rapier(default_gravity_dir) * default_gravity_value;
         if let Some(space) = physics_data.spaces.get_mut(space_rid) {
+            space.solve_joint_couplings(
+                settings.dt,
+                &mut physics_data.joints,
+                &mut physics_data.physics_engine,
+            );
             // this calls into rapier
             physics_data.physics_engine.world_step(
                 space_handle,
@@ -569,6 +579,103 @@ impl RapierSpace {
                 &mut physics_data.collision_objects,
                 &physics_data.ids,
             );
+            space.break_overloaded_joints(
+                &mut physics_data.joints,
+                &mut physics_data.physics_engine,
+                &physics_data.ids,
+            );
+        }
+    }
+
+    /// Gear, rack-and-pinion and pulley joints have no rapier counterpart, so their velocities
+    /// are corrected here before rapier steps the space. They are solved by joint id so the
+    /// result does not depend on the order of the joint map.
+    fn solve_joint_couplings(
+        &self,
+        dt: rapier::math::Real,
+        physics_joints: &mut PhysicsJoints,
+        physics_engine: &mut PhysicsEngine,
+    ) {
+        let space_handle = self.state.get_id();
+        let mut couplings: Vec<(RapierId, JointHandle, &mut JointCoupling)> = physics_joints
+            .values_mut()
+            .map(|joint| joint.get_mut_base())
+            .filter(|joint| joint.get_space_id() == space_handle)
+            .filter_map(|joint| {
+                let (id, handle) = (joint.get_id(), joint.get_handle());
+                joint
+                    .get_mut_coupling()
+                    .map(|coupling| (id, handle, coupling))
+            })
+            .collect();
+        if couplings.is_empty() {
+            return;
+        }
+        couplings.sort_unstable_by_key(|(id, ..)| *id);
+        let mut couplings: Vec<(JointHandle, &mut JointCoupling)> = couplings
+            .into_iter()
+            .map(|(_, handle, coupling)| (handle, coupling))
+            .collect();
+        physics_engine.joint_solve_couplings(space_handle, &mut couplings, dt, COUPLING_ITERATIONS);
+    }
+
+    /// Disables the breakable joints of this space whose reaction impulse on the last step went
+    /// over their break threshold, and queues them by id for
+    /// [`RapierSpaceState::take_broken_joint_list`]. Joints are checked in id order, so joints
+    /// breaking on the same step are queued in the order they were created.
+    fn break_overloaded_joints(
+        &mut self,
+        physics_joints: &mut PhysicsJoints,
+        physics_engine: &mut PhysicsEngine,
+        physics_ids: &PhysicsIds,
+    ) {
+        let space_handle = self.state.get_id();
+        let mut broken_joints = Vec::new();
+        for joint_id in self.state.get_breakable_joint_list() {
+            let Some(joint) = physics_joints.get_mut(&get_id_rid(*joint_id, physics_ids)) else {
+                continue;
+            };
+            let joint = joint.get_mut_base();
+            if joint.get_space_id() != space_handle || !joint.is_valid() || !joint.is_breakable() {
+                continue;
+            }
+            let (impulse, angular_impulse) =
+                physics_engine.joint_get_reaction_impulse(space_handle, joint.get_handle());
+            #[cfg(feature = "dim2")]
+            let angular_impulse = angular_impulse.abs();
+            #[cfg(feature = "dim3")]
+            let angular_impulse = angular_impulse.length();
+            if joint.exceeds_break_threshold(impulse.length(), angular_impulse) {
+                physics_engine.joint_set_enabled(space_handle, joint.get_handle(), false);
+                joint.set_broken(true);
+                broken_joints.push(*joint_id);
+            }
+        }
+        for joint in broken_joints {
+            self.state.joint_add_to_broken_list(joint);
+        }
+    }
+
+    /// Files `joint` under the breakable joints of the space it is in, and only there. Call it
+    /// whenever a joint is rebuilt or its break threshold changes, and with `None` when it is
+    /// freed.
+    pub fn update_breakable_joint(
+        joint_id: RapierId,
+        joint: Option<&RapierJointBase>,
+        physics_spaces: &mut PhysicsSpaces,
+        physics_ids: &PhysicsIds,
+    ) {
+        for space in physics_spaces.values_mut() {
+            space
+                .get_mut_state()
+                .joint_remove_from_breakable_list(joint_id);
+        }
+        if let Some(joint) = joint
+            && joint.is_valid()
+            && joint.has_break_threshold()
+            && let Some(space) = physics_spaces.get_mut(&joint.get_space(physics_ids))
+        {
+            space.get_mut_state().joint_add_to_breakable_list(joint_id);
         }
     }
 
@@ -69,6 +69,8 @@ pub struct RapierSpaceState {
     monitor_query_list: BTreeSet<RapierId>,
     area_update_list: BTreeSet<RapierId>,
     body_area_update_list: BTreeSet<RapierId>,
+    breakable_joint_list: BTreeSet<RapierId>,
+    broken_joint_list: Vec<RapierId>,
     time_stepped: f32,
     active_objects: i32,
     id: WorldHandle,
@@ -158,6 +160,22 @@ impl RapierSpaceState {
         self.body_area_update_list.remove(&body);
     }
 
+    pub fn joint_add_to_breakable_list(&mut self, joint: RapierId) {
+        self.breakable_joint_list.insert(joint);
+    }
+
+    pub fn joint_remove_from_breakable_list(&mut self, joint: RapierId) {
+        self.breakable_joint_list.remove(&joint);
+    }
+
+    pub fn joint_add_to_broken_list(&mut self, joint: RapierId) {
+        self.broken_joint_list.push(joint);
+    }
+
+    pub fn take_broken_joint_list(&mut self) -> Vec<RapierId> {
+        std::mem::take(&mut self.broken_joint_list)
+    }
+
     pub fn add_removed_collider(
         &mut self,
         handle: ColliderHandle,
@@ -219,6 +237,10 @@ impl RapierSpaceState {
         &self.body_area_update_list
     }
 
+    pub fn get_breakable_joint_list(&self) -> &BTreeSet<RapierId> {
+        &self.breakable_joint_list
+    }
+
     pub fn get_gravity_update_list(&self) -> &BTreeSet<RapierId> {
         &self.gravity_update_list
     }
@@ -403,6 +425,25 @@ mod tests {
         assert!(!state.get_area_update_list().contains(&rb_id));
     }
     #[test]
+    fn test_joint_add_and_remove_from_breakable_list() {
+        let mut physics_engine = PhysicsEngine::default();
+        let mut state = RapierSpaceState::new(0, &mut physics_engine, &create_world_settings());
+        state.joint_add_to_breakable_list(2);
+        state.joint_add_to_breakable_list(1);
+        assert!(state.get_breakable_joint_list().iter().eq([1, 2].iter()));
+        state.joint_remove_from_breakable_list(2);
+        assert!(state.get_breakable_joint_list().iter().eq([1].iter()));
+    }
+    #[test]
+    fn test_joint_add_to_and_take_broken_list() {
+        let mut physics_engine = PhysicsEngine::default();
+        let mut state = RapierSpaceState::new(0, &mut physics_engine, &create_world_settings());
+        state.joint_add_to_broken_list(2);
+        state.joint_add_to_broken_list(1);
+        assert_eq!(state.take_broken_joint_list(), vec![2, 1]);
+        assert!(state.take_broken_joint_list().is_empty());
+    }
+    #[test]
     fn test_add_removed_collider() {
         let mut physics_engine = PhysicsEngine::default();
         let mut state = RapierSpaceState::new(0, &mut physics_engine, &create_world_settings());
@@ -19,6 +19,10 @@ pub type Vector = godot::prelude::Vector3;
 #[cfg(feature = "dim2")]
 pub type Vector = godot::prelude::Vector2;
 #[cfg(feature = "dim3")]
+pub type Vectori = godot::prelude::Vector3i;
+#[cfg(feature = "dim2")]
+pub type Vectori = godot::prelude::Vector2i;
+#[cfg(feature = "dim3")]
 pub type Angle = godot::prelude::Vector3;
 #[cfg(feature = "dim2")]
 pub type Angle = real;
@@ -331,6 +335,63 @@ pub fn basis_to_rapier(basis: godot::builtin::Basis) -> Rotation {
     );
     Rotation::from_mat3(&rot_matrix)
 }
+/// Splits a basis whose columns are not orthogonal into the rotation of its Gram-Schmidt
+/// orthonormalised columns, the scale along them and the shear to apply to a shape before that
+/// scale. `None` when the columns are orthogonal, which [`basis_to_rapier`] and
+/// [`transform_scale`] handle.
+#[cfg(feature = "dim3")]
+pub fn basis_shear_to_rapier(
+    basis: godot::builtin::Basis,
+) -> Option<(Rotation, Vector3, rapier::prelude::Mat3)> {
+    let dot = |a: Vector3, b: Vector3| -> real { a.x * b.x + a.y * b.y + a.z * b.z };
+    let length = |a: Vector3| -> real { ComplexField::sqrt(dot(a, a)) };
+    let col0 = basis.col_a();
+    let col1 = basis.col_b();
+    let col2 = basis.col_c();
+    let sx = length(col0);
+    if sx == 0.0 {
+        return None;
+    }
+    let axis0 = col0 / sx;
+    let shear01 = dot(axis0, col1);
+    let ortho1 = col1 - axis0 * shear01;
+    let sy = length(ortho1);
+    if sy == 0.0 {
+        return None;
+    }
+    let axis1 = ortho1 / sy;
+    let shear02 = dot(axis0, col2);
+    let shear12 = dot(axis1, col2);
+    let ortho2 = col2 - axis0 * shear02 - axis1 * shear12;
+    let sz = length(ortho2);
+    if sz == 0.0 {
+        return None;
+    }
+    let axis2 = ortho2 / sz;
+    // Shear relative to the scale of the axis it is added to, so it applies before the scale.
+    let (shear01, shear02, shear12) = (shear01 / sx, shear02 / sx, shear12 / sy);
+    if shear01.is_zero_approx() && shear02.is_zero_approx() && shear12.is_zero_approx() {
+        return None;
+    }
+    // Account for negative determinant (reflection) by flipping the first axis, whose scale
+    // turns negative as in transform_scale.
+    let sign = if basis.determinant() < 0.0 { -1.0 } else { 1.0 };
+    let rot_matrix = rapier::prelude::Mat3::from_cols(
+        rapier::prelude::Vec3::new(axis0.x * sign, axis0.y * sign, axis0.z * sign),
+        rapier::prelude::Vec3::new(axis1.x, axis1.y, axis1.z),
+        rapier::prelude::Vec3::new(axis2.x, axis2.y, axis2.z),
+    );
+    let shear = rapier::prelude::Mat3::from_cols(
+        rapier::prelude::Vec3::X,
+        rapier::prelude::Vec3::new(shear01, 1.0, 0.0),
+        rapier::prelude::Vec3::new(shear02, shear12, 1.0),
+    );
+    Some((
+        Rotation::from_mat3(&rot_matrix),
+        Vector3::new(sx * sign, sy, sz),
+        shear,
+    ))
+}
 pub fn vector_length(vector: Vector) -> real {
     #[cfg(feature = "dim2")]
     {